@@ -1,8 +1,11 @@
 use crate::alloc::{format, vec, BTreeMap, Entry, String, ToString};
 use crate::build::dyn_builder::DynCallPatternBuilder;
 use crate::call_pattern::CallPattern;
+use crate::debug::TraitMethodPath;
+use crate::factory::TypeFactory;
 use crate::fn_mocker::{FnMocker, PatternMatchMode};
 use crate::output::OutputError;
+use crate::state::Layer;
 use crate::Clause;
 use crate::{clause, MockFnInfo};
 
@@ -10,6 +13,7 @@ use core::any::TypeId;
 
 pub(crate) struct MockAssembler {
     fn_mockers: BTreeMap<TypeId, FnMocker>,
+    type_factories: BTreeMap<TypeId, TypeFactory>,
     current_call_index: usize,
 }
 
@@ -17,19 +21,34 @@ impl MockAssembler {
     #[inline]
     #[track_caller]
     pub fn try_from_clause(clause: impl Clause) -> Result<Self, String> {
-        let mut assembler = Self::new();
+        Self::try_from_clause_at(clause, 0)
+    }
+
+    /// Assemble a clause whose ordered call patterns start at `first_call_index` in the call order.
+    #[inline]
+    #[track_caller]
+    pub fn try_from_clause_at(
+        clause: impl Clause,
+        first_call_index: usize,
+    ) -> Result<Self, String> {
+        let mut assembler = Self::new(first_call_index);
         clause.deconstruct(&mut assembler).map(|_| assembler)
     }
 
-    fn new() -> Self {
+    fn new(first_call_index: usize) -> Self {
         Self {
             fn_mockers: BTreeMap::new(),
-            current_call_index: 0,
+            type_factories: BTreeMap::new(),
+            current_call_index: first_call_index,
         }
     }
 
-    pub fn finish(self) -> BTreeMap<TypeId, FnMocker> {
-        self.fn_mockers
+    pub fn finish(self) -> Layer {
+        Layer::new(
+            self.fn_mockers,
+            self.type_factories,
+            self.current_call_index,
+        )
     }
 }
 
@@ -45,6 +64,13 @@ impl clause::term::Sink for MockAssembler {
             });
         }
 
+        if !builder.pending_arg_writers.is_empty() {
+            return Err(format!(
+                "{path}: writes_arg must be followed by a response.",
+                path = info.path
+            ));
+        }
+
         let pattern_match_mode = builder.pattern_match_mode;
         let mock_type_id = info.type_id;
 
@@ -76,6 +102,24 @@ impl clause::term::Sink for MockAssembler {
 
         Ok(())
     }
+
+    fn push_type_factory(
+        &mut self,
+        untyped_type_id: TypeId,
+        path: &'static [&'static str; 2],
+        factory: TypeFactory,
+    ) -> Result<(), String> {
+        match self.type_factories.entry(untyped_type_id) {
+            Entry::Occupied(_) => Err(format!(
+                "A type factory for {path} has already been registered.",
+                path = TraitMethodPath::from_path(path),
+            )),
+            Entry::Vacant(entry) => {
+                entry.insert(factory);
+                Ok(())
+            }
+        }
+    }
 }
 
 impl MockAssembler {
@@ -99,6 +143,7 @@ impl MockAssembler {
             responders: builder.responders,
             ordered_call_index_range,
             call_counter: builder.count_expectation.into_counter(),
+            from_fixture: builder.from_fixture,
         }
     }
 }
@@ -1,10 +1,14 @@
+use core::borrow::Borrow;
 use core::marker::PhantomData;
 
 use crate::alloc::vec;
 use crate::alloc::{String, ToString, Vec};
 use crate::call_pattern::*;
 use crate::fn_mocker::PatternMatchMode;
-use crate::output::{IntoReturn, IntoReturnOnce, Return, ReturnDefault};
+use crate::input::{ArgWriter, MutArg, WriteArg};
+use crate::output::{
+    IntoReturn, IntoReturnOnce, LendingAnswer, Return, ReturnDefault, ReturnsItems,
+};
 use crate::private::{AnswerClosure, AnswerClosureInner};
 use crate::property::*;
 use crate::responder::{Answerer, DynResponder, IntoReturner};
@@ -18,7 +22,7 @@ pub(crate) mod dyn_builder {
     use crate::MockFn;
 
     use crate::{
-        call_pattern::{DynCallOrderResponder, DynInputMatcher},
+        call_pattern::{DynArgWriter, DynCallOrderResponder, DynInputMatcher},
         counter,
         fn_mocker::PatternMatchMode,
         responder::DynResponder,
@@ -29,9 +33,11 @@ pub(crate) mod dyn_builder {
         pub(crate) pattern_match_mode: PatternMatchMode,
         pub(crate) input_matcher: DynInputMatcher,
         pub(crate) responders: Vec<DynCallOrderResponder>,
+        pub(crate) pending_arg_writers: Vec<DynArgWriter>,
         pub(crate) count_expectation: counter::CallCountExpectation,
         pub(crate) current_response_index: usize,
         pub(crate) responder_error: Option<OutputError>,
+        pub(crate) from_fixture: bool,
     }
 
     impl DynCallPatternBuilder {
@@ -43,9 +49,11 @@ pub(crate) mod dyn_builder {
                 pattern_match_mode,
                 input_matcher,
                 responders: vec![],
+                pending_arg_writers: vec![],
                 count_expectation: Default::default(),
                 current_response_index: 0,
                 responder_error: None,
+                from_fixture: false,
             }
         }
     }
@@ -96,12 +104,19 @@ pub(crate) mod dyn_builder {
 
         pub fn push_responder(&mut self, responder: DynResponder) {
             let dyn_builder = self.inner_mut();
+            let arg_writers = core::mem::take(&mut dyn_builder.pending_arg_writers);
             dyn_builder.responders.push(DynCallOrderResponder {
                 response_index: dyn_builder.current_response_index,
                 responder,
+                arg_writers,
             })
         }
 
+        /// Register an argument writer, which will be attached to the next pushed responder.
+        pub fn push_arg_writer(&mut self, arg_writer: DynArgWriter) {
+            self.inner_mut().pending_arg_writers.push(arg_writer);
+        }
+
         /// Note: must be called after `push_responder`
         pub fn quantify(&mut self, times: usize, exactness: counter::Exactness) {
             let builder = self.inner_mut();
@@ -122,6 +137,7 @@ pub(crate) mod dyn_builder {
 /// Builder for defining a series of cascading call patterns on a specific [MockFn].
 pub struct Each<F: MockFn> {
     patterns: Vec<dyn_builder::DynCallPatternBuilder>,
+    tag: Option<&'static str>,
     mock_fn: PhantomData<F>,
 }
 
@@ -140,7 +156,7 @@ where
     ) -> DefineMultipleResponses<'e, F, InAnyOrder> {
         self.patterns.push(dyn_builder::DynCallPatternBuilder::new(
             PatternMatchMode::InAnyOrder,
-            DynInputMatcher::from_matching_fn(matching_fn),
+            DynInputMatcher::from_matching_fn(matching_fn).tagged(self.tag),
         ));
 
         DefineMultipleResponses {
@@ -150,9 +166,10 @@ where
         }
     }
 
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(tag: Option<&'static str>) -> Self {
         Self {
             patterns: vec![],
+            tag,
             mock_fn: PhantomData,
         }
     }
@@ -175,6 +192,73 @@ where
     }
 }
 
+/// A [MockFn] scoped to the [Unimock] instances tagged with a specific tag.
+///
+/// Created by [MockFn::tag]. Call patterns defined through it only match calls on instances created with [Unimock::tagged].
+pub struct Tagged<F: MockFn> {
+    tag: &'static str,
+    mock_fn: PhantomData<F>,
+}
+
+impl<F> Tagged<F>
+where
+    F: MockFn,
+{
+    pub(crate) fn new(tag: &'static str) -> Self {
+        Self {
+            tag,
+            mock_fn: PhantomData,
+        }
+    }
+
+    /// Create a stubbing clause for the tagged instance. See [MockFn::stub].
+    #[track_caller]
+    pub fn stub<E>(self, each_fn: E) -> Each<F>
+    where
+        E: FnOnce(&mut Each<F>),
+    {
+        let mut each = Each::new(Some(self.tag));
+        each_fn(&mut each);
+        each
+    }
+
+    /// Define a call pattern for the tagged instance. See [MockFn::some_call].
+    pub fn some_call(
+        self,
+        matching_fn: &dyn Fn(&mut Matching<F>),
+    ) -> DefineResponse<'static, F, InAnyOrder> {
+        DefineResponse::with_owned_builder(
+            DynInputMatcher::from_matching_fn(matching_fn).tagged(Some(self.tag)),
+            PatternMatchMode::InAnyOrder,
+            InAnyOrder,
+        )
+    }
+
+    /// Define a call pattern for the tagged instance, specialized for functions called multiple times. See [MockFn::each_call].
+    pub fn each_call(
+        self,
+        matching_fn: &dyn Fn(&mut Matching<F>),
+    ) -> DefineMultipleResponses<'static, F, InAnyOrder> {
+        DefineMultipleResponses::with_owned_builder(
+            DynInputMatcher::from_matching_fn(matching_fn).tagged(Some(self.tag)),
+            PatternMatchMode::InAnyOrder,
+            InAnyOrder,
+        )
+    }
+
+    /// Define an ordered call pattern for the tagged instance. See [MockFn::next_call].
+    pub fn next_call(
+        self,
+        matching_fn: &dyn Fn(&mut Matching<F>),
+    ) -> DefineResponse<'static, F, InOrder> {
+        DefineResponse::with_owned_builder(
+            DynInputMatcher::from_matching_fn(matching_fn).tagged(Some(self.tag)),
+            PatternMatchMode::InOrder,
+            InOrder,
+        )
+    }
+}
+
 /// A matched call pattern, ready for defining a single response.
 pub struct DefineResponse<'p, F: MockFn, O: Ordering> {
     wrapper: DynBuilderWrapper<'p>,
@@ -293,6 +377,50 @@ macro_rules! define_response_common_impl {
                 }
             }
 
+            /// Write a value into the `&mut` parameter with index `N` before responding.
+            ///
+            /// The parameter index excludes the `self` receiver.
+            /// The write happens each time the response that follows is produced,
+            /// so it may be combined with any other response, like [`returns`](Self::returns).
+            /// A `writes_arg` that is not followed by a response makes the clause invalid.
+            ///
+            /// A slice parameter gets the value copied into its start, and keeps the elements past the length of the value.
+            /// A value that is longer than the slice fails the call with a mock error.
+            ///
+            /// # Example
+            #[doc = concat!("\
+```
+# use unimock::*;
+#[unimock(api=TraitMock)]
+trait Trait {
+    fn read(&self, buf: &mut [u8]) -> usize;
+}
+
+let u = Unimock::new(
+    TraitMock::read
+        .next_call(matching!(_))
+        .writes_arg::<0>(b\"hello\")
+        .returns(5_usize)
+);
+
+let mut buf = [0; 8];
+assert_eq!(5, u.read(&mut buf));
+assert_eq!(b\"hello\", &buf[..5]);
+```
+",
+)]
+            pub fn writes_arg<const N: usize>(
+                mut self,
+                value: impl WriteArg<<F as MutArg<N>>::Target> + Send + Sync + 'static,
+            ) -> Self
+            where
+                F: MutArg<N>,
+            {
+                self.wrapper
+                    .push_arg_writer(DynArgWriter::new(ArgWriter::<F>::new::<N, _>(value)));
+                self
+            }
+
             /// Specify the response of the call pattern by calling `Default::default()`.
             ///
             /// # Example
@@ -328,6 +456,49 @@ assert_eq!(None, u.get());
                 self.quantify()
             }
 
+            /// Specify the response of the call pattern as a sequence of items.
+            ///
+            /// This is used with methods returning `Box<dyn Iterator<Item = T>>` or `Pin<Box<dyn Stream<Item = T>>>`.
+            /// Each matching call produces a fresh iterator (or stream) over clones of the given items.
+            /// When the items are references borrowed from `self`, the given items are the values behind the references.
+            ///
+            /// A boxed iterator or stream of owned items can still be responded to with [`returns`](Self::returns),
+            /// but only once per value, as boxed iterators can't be cloned.
+            ///
+            /// Streams require the `futures-core-0-3` feature.
+            ///
+            /// # Example
+            #[doc = concat!("\
+```
+# use unimock::*;
+#[unimock(api=TraitMock)]
+trait Trait {
+    fn page(&self, index: usize) -> Box<dyn Iterator<Item = String>>;
+}
+
+let u = Unimock::new(
+    TraitMock::page
+        .each_call(matching!(0))
+        .returns_items([\"a\".to_string(), \"b\".to_string()])
+);
+
+assert_eq!(vec![\"a\", \"b\"], u.page(0).collect::<Vec<_>>());
+assert_eq!(2, u.page(0).count());
+```
+",
+)]
+            pub fn returns_items<I>(mut self, items: I) -> Quantify<'p, F, O>
+            where
+                F: ReturnsItems,
+                I: IntoIterator<Item = <F as ReturnsItems>::Item>,
+                <<F as MockFn>::OutputKind as Kind>::Return: IntoReturner<F>,
+            {
+                self.wrapper.push_returner_result(
+                    F::items_return(items.into_iter().collect()).map(|r| r.into_returner()),
+                );
+                self.quantify()
+            }
+
             /// Specify the response of the call pattern by applying the given function that can then compute it based on input parameters.
             ///
             /// The applied function can respond with types that don't implement [Send] and [Sync].
@@ -430,6 +601,54 @@ assert_eq!(55, u.fib(10));
                 self.quantify()
             }
 
+            /// Specify the response of the call pattern by applying the given function, which computes an owned value that gets lent out.
+            ///
+            /// This is an alternative to [`answers`](Self::answers) for methods returning `&T`, `Option<&T>` or `Result<&T, E>` borrowed from `self`.
+            /// In place of each `&T`, the function returns a value that can be [borrowed](core::borrow::Borrow) as `T`,
+            /// and Unimock keeps it alive for as long as the reference is in use, like [`make_ref`](crate::Unimock::make_ref) does.
+            ///
+            /// `answers` keeps taking functions with the exact signature of the mocked method,
+            /// so `answers_owned` is only available for these output shapes. Other outputs that borrow from `self`,
+            /// like `&mut T`, can still be answered with [`answers`](Self::answers) and [`make_ref`](crate::Unimock::make_ref)/[`make_mut`](crate::Unimock::make_mut).
+            ///
+            /// # Example
+            #[doc = concat!("\
+```
+# use unimock::*;
+#[unimock(api=TraitMock)]
+trait Trait {
+    fn get(&self, key: i32) -> &str;
+}
+
+let u = Unimock::new(
+    TraitMock::get
+        .each_call(matching!(_))
+        .answers_owned(&|_, key| format!(\"v-{key}\"))
+);
+
+assert_eq!(\"v-42\", u.get(42));
+```
+",
+)]
+            pub fn answers_owned<T>(
+                mut self,
+                answer_fn: &'static <F as LendingAnswer>::OwnedAnswerFn<T>,
+            ) -> Quantify<'p, F, O>
+            where
+                F: LendingAnswer,
+                T: Borrow<<F as LendingAnswer>::Lent> + Send + Sync + 'static,
+            {
+                self.wrapper.push_responder(
+                    Answerer::<F> {
+                        answer_closure: AnswerClosure(AnswerClosureInner::Arc(F::lend_answer(
+                            answer_fn,
+                        ))),
+                    }
+                    .into_dyn_responder(),
+                );
+                self.quantify()
+            }
+
             /// Specify the response of the call pattern by invoking the given closure that can then compute it based on input parameters.
             #[doc = concat!("\
 ```
@@ -1,4 +1,5 @@
 use crate::alloc::Vec;
+use crate::input::ArgWriter;
 use crate::responder::DynResponder;
 
 use crate::private::MismatchReporter;
@@ -32,6 +33,8 @@ pub(crate) struct CallPattern {
     pub responders: Vec<DynCallOrderResponder>,
     pub ordered_call_index_range: core::ops::Range<usize>,
     pub call_counter: counter::CallCounter,
+    /// Whether the pattern came from a [Fixture](crate::Fixture). Mock fns with only such patterns are verified as with [Policy::allow_unused](crate::Policy::allow_unused).
+    pub from_fixture: bool,
 }
 
 impl CallPattern {
@@ -60,7 +63,7 @@ impl CallPattern {
         }
     }
 
-    pub fn next_responder(&self) -> Option<&DynResponder> {
+    pub fn next_responder(&self) -> Option<&DynCallOrderResponder> {
         find_responder_by_call_index(&self.responders, self.call_counter.fetch_add())
     }
 }
@@ -68,6 +71,8 @@ impl CallPattern {
 pub(crate) struct DynInputMatcher {
     dyn_matching_fn: Option<DynMatchingFn>,
     pub(crate) matcher_debug: Option<debug::InputMatcherDebug>,
+    /// Only calls on a [Unimock] instance with the same tag can match.
+    pub(crate) tag: Option<&'static str>,
 }
 
 impl DynInputMatcher {
@@ -78,8 +83,14 @@ impl DynInputMatcher {
         Self {
             dyn_matching_fn: builder.matching_fn.map(|f| DynMatchingFn(Box::new(f))),
             matcher_debug: builder.matcher_debug,
+            tag: None,
         }
     }
+
+    pub fn tagged(mut self, tag: Option<&'static str>) -> Self {
+        self.tag = tag;
+        self
+    }
 }
 
 struct DynMatchingFn(AnyBox);
@@ -92,12 +103,26 @@ pub(crate) struct MatchingFn<F: MockFn>(
 pub(crate) struct DynCallOrderResponder {
     pub response_index: usize,
     pub responder: DynResponder,
+    pub arg_writers: Vec<DynArgWriter>,
+}
+
+/// Type-erased [ArgWriter].
+pub(crate) struct DynArgWriter(AnyBox);
+
+impl DynArgWriter {
+    pub fn new<F: MockFn>(arg_writer: ArgWriter<F>) -> Self {
+        Self(Box::new(arg_writer))
+    }
+
+    pub fn downcast<F: MockFn>(&self) -> PatternResult<&ArgWriter<F>> {
+        downcast_box(&self.0)
+    }
 }
 
 fn find_responder_by_call_index(
     responders: &[DynCallOrderResponder],
     call_index: usize,
-) -> Option<&DynResponder> {
+) -> Option<&DynCallOrderResponder> {
     if responders.is_empty() {
         return None;
     }
@@ -106,8 +131,8 @@ fn find_responder_by_call_index(
         responders.binary_search_by(|responder| responder.response_index.cmp(&call_index));
 
     Some(match index_result {
-        Ok(index) => &responders[index].responder,
-        Err(insert_index) => &responders[insert_index - 1].responder,
+        Ok(index) => &responders[index],
+        Err(insert_index) => &responders[insert_index - 1],
     })
 }
 
@@ -123,17 +148,21 @@ mod tests {
             DynCallOrderResponder {
                 response_index: 0,
                 responder: DynResponder::Panic("0".into()),
+                arg_writers: vec![],
             },
             DynCallOrderResponder {
                 response_index: 5,
                 responder: DynResponder::Panic("5".into()),
+                arg_writers: vec![],
             },
         ];
 
         fn find_msg(responders: &[DynCallOrderResponder], call_index: usize) -> Option<&str> {
-            find_responder_by_call_index(responders, call_index).map(|responder| match responder {
-                DynResponder::Panic(msg) => msg.as_ref(),
-                _ => panic!(),
+            find_responder_by_call_index(responders, call_index).map(|responder| {
+                match &responder.responder {
+                    DynResponder::Panic(msg) => msg.as_ref(),
+                    _ => panic!(),
+                }
             })
         }
 
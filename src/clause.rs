@@ -4,10 +4,17 @@ use crate::*;
 pub(crate) mod term {
     use crate::alloc::String;
 
-    use crate::{build::dyn_builder::DynCallPatternBuilder, MockFnInfo};
+    use crate::{build::dyn_builder::DynCallPatternBuilder, factory::TypeFactory, MockFnInfo};
 
     pub trait Sink {
         fn push(&mut self, info: MockFnInfo, builder: DynCallPatternBuilder) -> Result<(), String>;
+
+        fn push_type_factory(
+            &mut self,
+            untyped_type_id: core::any::TypeId,
+            path: &'static [&'static str; 2],
+            factory: TypeFactory,
+        ) -> Result<(), String>;
     }
 }
 
@@ -1,11 +1,15 @@
 use crate::alloc::{format, Vec};
 use crate::error::MockError;
+use crate::policy::{self, Policy};
 use crate::{debug, MockFnInfo};
 
-use core::{fmt::Display, sync::atomic::AtomicUsize};
+use core::fmt::Display;
+use core::sync::atomic::{AtomicBool, AtomicUsize};
 
 pub(crate) struct CallCounter {
     actual_count: AtomicUsize,
+    /// Whether a checkpoint has verified the expectation, which ends the epoch the counter was set up in.
+    epoch_ended: AtomicBool,
     expectation: CallCountExpectation,
 }
 
@@ -15,21 +19,52 @@ impl CallCounter {
             .fetch_add(1, core::sync::atomic::Ordering::SeqCst)
     }
 
+    pub fn actual_count(&self) -> usize {
+        self.actual_count.load(core::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// End the epoch of the counter, after its expectation has been verified by a checkpoint.
+    ///
+    /// Later calls are still counted, but no longer verified against the expectation.
+    pub fn end_epoch(&self) {
+        self.epoch_ended
+            .store(true, core::sync::atomic::Ordering::SeqCst);
+    }
+
+    pub fn epoch_ended(&self) -> bool {
+        self.epoch_ended.load(core::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Whether a checkpoint has verified an exact call count, after which the call pattern no longer matches calls.
+    pub fn is_retired(&self) -> bool {
+        self.epoch_ended() && self.expectation.exact_calls().is_some()
+    }
+
     pub fn verify(
         &self,
         info: &MockFnInfo,
         debug_fn: impl Fn() -> debug::CallPatternDebug,
+        policy: &Policy,
         errors: &mut Vec<MockError>,
     ) -> NCalls {
         let path = &info.path;
         let actual_calls = NCalls(self.actual_count.load(core::sync::atomic::Ordering::SeqCst));
+        if self.epoch_ended() {
+            return actual_calls;
+        }
+
         let lower_bound = self.expectation.lower_bound();
 
         match self.expectation.exactness {
             Exactness::Exact => {
                 if actual_calls.0 != lower_bound.0 {
                     let pattern = debug_fn();
-                    errors.push(MockError::FailedVerification(format!("{path}: Expected {pattern} to match exactly {lower_bound}, but it actually matched {actual_calls}.")));
+                    let error = MockError::FailedVerification(format!("{path}: Expected {pattern} to match exactly {lower_bound}, but it actually matched {actual_calls}."));
+                    if actual_calls.0 > lower_bound.0 && policy.extra_calls_as_warnings {
+                        policy::warn(&error);
+                    } else {
+                        errors.push(error);
+                    }
                 }
             }
             Exactness::AtLeast | Exactness::AtLeastPlusOne => {
@@ -76,6 +111,7 @@ impl CallCountExpectation {
     pub fn into_counter(self) -> CallCounter {
         CallCounter {
             actual_count: AtomicUsize::new(0),
+            epoch_ended: AtomicBool::new(false),
             expectation: self,
         }
     }
@@ -0,0 +1,82 @@
+use core::fmt::Display;
+use core::sync::atomic::Ordering;
+
+use crate::alloc::Vec;
+use crate::state::SharedState;
+use crate::MockFnInfo;
+
+/// Which mocked methods of some traits were exercised by a [Unimock](crate::Unimock) instance.
+///
+/// Created by [Unimock::coverage_report](crate::Unimock::coverage_report).
+/// Its [Display] implementation lists the uncovered methods, one per line.
+pub struct CoverageReport {
+    never_stubbed: Vec<MockFnInfo>,
+    never_called: Vec<MockFnInfo>,
+}
+
+impl CoverageReport {
+    pub(crate) fn new(shared_state: &SharedState, mock_fns: &[&[fn() -> MockFnInfo]]) -> Self {
+        let mut never_stubbed = Vec::new();
+        let mut never_called = Vec::new();
+
+        for info in mock_fns.iter().flat_map(|infos| infos.iter()).map(|f| f()) {
+            let fn_mockers = shared_state
+                .layers()
+                .flat_map(|layer| layer.fn_mockers.values())
+                .filter(|fn_mocker| fn_mocker.info.is_covered_by(&info));
+            let type_factories = shared_state
+                .layers()
+                .filter_map(|layer| layer.type_factories.get(&info.type_id));
+
+            let mut stubbed = false;
+            let mut calls = None;
+            for type_factory in type_factories {
+                stubbed = true;
+                *calls.get_or_insert(0) += type_factory.calls.load(Ordering::SeqCst);
+            }
+            for fn_mocker in fn_mockers {
+                stubbed = true;
+                *calls.get_or_insert(0) += fn_mocker.actual_calls();
+            }
+
+            if !stubbed {
+                never_stubbed.push(info);
+            } else if calls == Some(0) {
+                never_called.push(info);
+            }
+        }
+
+        Self {
+            never_stubbed,
+            never_called,
+        }
+    }
+
+    /// Methods that had no clauses and no type factory.
+    pub fn never_stubbed(&self) -> &[MockFnInfo] {
+        &self.never_stubbed
+    }
+
+    /// Methods that were stubbed, but never called.
+    pub fn never_called(&self) -> &[MockFnInfo] {
+        &self.never_called
+    }
+
+    /// Whether every method was both stubbed and called.
+    pub fn is_complete(&self) -> bool {
+        self.never_stubbed.is_empty() && self.never_called.is_empty()
+    }
+}
+
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for info in &self.never_stubbed {
+            writeln!(f, "{}: never stubbed", info.path)?;
+        }
+        for info in &self.never_called {
+            writeln!(f, "{}: stubbed, but never called", info.path)?;
+        }
+
+        Ok(())
+    }
+}
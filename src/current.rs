@@ -0,0 +1,56 @@
+//! The "current" [Unimock] instance of a thread, used for mocking associated functions without a `self` receiver.
+
+use crate::{MockFn, Unimock};
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static CURRENT: core::cell::RefCell<crate::alloc::Vec<Unimock>> = const { core::cell::RefCell::new(crate::alloc::Vec::new()) };
+}
+
+/// Guard returned from [Unimock::enter].
+///
+/// The instance stays current on the thread until the guard is dropped.
+/// The guard must be dropped before the original [Unimock] instance, which is the case when declared after it.
+#[cfg(feature = "std")]
+#[must_use = "the instance stops being current when the guard is dropped"]
+pub struct EnterGuard {
+    // The guard must be dropped on the thread that created it
+    _not_send: core::marker::PhantomData<*const ()>,
+}
+
+#[cfg(feature = "std")]
+impl Drop for EnterGuard {
+    fn drop(&mut self) {
+        let unimock = CURRENT.with(|current| current.borrow_mut().pop());
+        drop(unimock);
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn enter(unimock: &Unimock) -> EnterGuard {
+    CURRENT.with(|current| current.borrow_mut().push(unimock.clone()));
+    EnterGuard {
+        _not_send: core::marker::PhantomData,
+    }
+}
+
+#[cfg(feature = "std")]
+#[track_caller]
+pub(crate) fn current<F: MockFn>() -> Unimock {
+    match CURRENT.with(|current| current.borrow().last().cloned()) {
+        Some(unimock) => unimock,
+        None => panic!(
+            "{}: No current Unimock instance on this thread. Associated functions without a self receiver are mocked by the instance passed to `Unimock::enter`.",
+            F::info().path
+        ),
+    }
+}
+
+#[cfg(not(feature = "std"))]
+#[track_caller]
+pub(crate) fn current<F: MockFn>() -> Unimock {
+    panic!(
+        "{}: Mocking associated functions without a self receiver requires the `std` feature.",
+        F::info().path
+    )
+}
@@ -1,12 +1,28 @@
 use core::fmt::Display;
 
 use crate::alloc::{Box, String};
+use crate::state::SharedState;
 use crate::{call_pattern::PatIndex, MockFnInfo};
 
 #[derive(Clone)]
 pub(crate) struct FnActualCall {
     pub info: MockFnInfo,
     pub inputs_debug: Box<[Option<String>]>,
+    /// The tagged instance, thread or task making the call, unless it is an untagged instance on the thread that created the [Unimock](crate::Unimock).
+    pub caller: Option<Box<Caller>>,
+}
+
+impl FnActualCall {
+    /// Whether the call came from another thread or task than the one that created the [Unimock](crate::Unimock).
+    fn is_concurrent(&self) -> bool {
+        match &self.caller {
+            #[cfg(feature = "std")]
+            Some(caller) if caller.thread.is_some() => true,
+            #[cfg(feature = "tokio-1-task-id")]
+            Some(caller) if caller.task.is_some() => true,
+            _ => false,
+        }
+    }
 }
 
 impl core::fmt::Display for FnActualCall {
@@ -23,7 +39,187 @@ impl core::fmt::Display for FnActualCall {
                 write!(f, ", ")?;
             }
         }
-        write!(f, ")")
+        write!(f, ")")?;
+
+        if let Some(caller) = &self.caller {
+            write!(f, "{caller}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt-1")]
+impl defmt::Format for FnActualCall {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{}(", self.info.path);
+
+        for (index, debug) in self.inputs_debug.iter().enumerate() {
+            if index > 0 {
+                defmt::write!(f, ", ");
+            }
+            match debug {
+                Some(debug) => defmt::write!(f, "{=str}", debug.as_str()),
+                None => defmt::write!(f, "?"),
+            }
+        }
+        defmt::write!(f, ")");
+
+        if let Some(tag) = self.caller.as_ref().and_then(|caller| caller.tag) {
+            defmt::write!(f, " on \"{=str}\"", tag);
+        }
+    }
+}
+
+/// The tagged instance, thread, and tokio task that made a call.
+#[derive(Clone)]
+pub(crate) struct Caller {
+    tag: Option<&'static str>,
+    #[cfg(feature = "std")]
+    thread: Option<CallerThread>,
+    #[cfg(feature = "tokio-1-task-id")]
+    task: Option<tokio_1::task::Id>,
+}
+
+impl Caller {
+    fn is_known(&self) -> bool {
+        if self.tag.is_some() {
+            return true;
+        }
+
+        #[cfg(feature = "std")]
+        if self.thread.is_some() {
+            return true;
+        }
+
+        #[cfg(feature = "tokio-1-task-id")]
+        if self.task.is_some() {
+            return true;
+        }
+
+        false
+    }
+}
+
+impl SharedState {
+    /// The current caller, if the instance is tagged, the thread differs from the one that created the [Unimock](crate::Unimock), or it runs inside a task.
+    pub fn current_caller(&self, tag: Option<&'static str>) -> Option<Box<Caller>> {
+        let caller = Caller {
+            tag,
+            #[cfg(feature = "std")]
+            thread: CallerThread::current(self.original_thread),
+            #[cfg(feature = "tokio-1-task-id")]
+            task: tokio_1::task::try_id(),
+        };
+
+        if caller.is_known() {
+            Some(Box::new(caller))
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Caller {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(tag) = self.tag {
+            write!(f, " on \"{tag}\"")?;
+        }
+
+        let places: &[Option<(&str, &dyn Display)>] = &[
+            #[cfg(feature = "std")]
+            self.thread
+                .as_ref()
+                .map(|thread| ("thread", thread as &dyn Display)),
+            #[cfg(feature = "tokio-1-task-id")]
+            self.task
+                .as_ref()
+                .map(|task| ("task", task as &dyn Display)),
+        ];
+
+        for (index, (kind, place)) in places.iter().flatten().enumerate() {
+            let separator = if index == 0 { " in " } else { ", " };
+            write!(f, "{separator}{kind} {place}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A thread other than the one that created the [Unimock](crate::Unimock).
+#[cfg(feature = "std")]
+#[derive(Clone)]
+struct CallerThread {
+    name: Option<String>,
+    id: std::thread::ThreadId,
+}
+
+#[cfg(feature = "std")]
+impl CallerThread {
+    fn current(original_thread: std::thread::ThreadId) -> Option<Self> {
+        let thread = std::thread::current();
+        if thread.id() == original_thread {
+            return None;
+        }
+
+        Some(Self {
+            name: thread.name().map(String::from),
+            id: thread.id(),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for CallerThread {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "'{name}'"),
+            None => write!(f, "{:?}", self.id),
+        }
+    }
+}
+
+/// A call to a mock fn in an ordered call pattern, at position `index` in the call order.
+#[derive(Clone)]
+pub(crate) struct OrderedCall {
+    pub index: usize,
+    pub fn_call: FnActualCall,
+}
+
+/// The most recent calls to mock fns in ordered call patterns.
+///
+/// Only present when some of the calls came from other threads or tasks.
+#[derive(Clone)]
+pub(crate) struct Timeline(Option<Box<Box<[OrderedCall]>>>);
+
+impl Timeline {
+    /// The number of calls kept for the timeline.
+    pub const MAX_CALLS: usize = 32;
+
+    pub fn new(calls: Box<[OrderedCall]>) -> Self {
+        if calls.iter().all(|call| !call.fn_call.is_concurrent()) {
+            Self(None)
+        } else {
+            Self(Some(Box::new(calls)))
+        }
+    }
+}
+
+impl Display for Timeline {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Some(calls) = &self.0 else {
+            return Ok(());
+        };
+
+        write!(f, "\nTimeline of ordered calls:")?;
+        if calls.first().is_some_and(|call| call.index > 0) {
+            write!(f, "\n  ...")?;
+        }
+        for call in calls.iter() {
+            write!(f, "\n  {}. {}", call.index + 1, call.fn_call)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -58,6 +254,13 @@ impl Display for TraitMethodPath {
     }
 }
 
+#[cfg(feature = "defmt-1")]
+impl defmt::Format for TraitMethodPath {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=str}::{=str}", self.trait_ident(), self.method_ident())
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct CallPatternDebug {
     inner: Box<CallPatternDebugInner>,
@@ -1,4 +1,5 @@
 use crate::alloc::{Box, String};
+use crate::input::WriteArgError;
 use crate::{debug, mismatch::Mismatches, MockFnInfo};
 
 pub(crate) type MockResult<T> = Result<T, MockError>;
@@ -12,6 +13,19 @@ pub(crate) enum MockError {
     NoMockImplementation {
         fn_call: debug::FnActualCall,
     },
+    NoMockImplementationForTypes {
+        fn_call: debug::FnActualCall,
+        requested: TypeArgs,
+        mocked: Box<[TypeArgs]>,
+    },
+    TypeFactoryMissingType {
+        fn_call: debug::FnActualCall,
+        requested: &'static str,
+        produced: Box<[&'static str]>,
+    },
+    TypeFactoryUnsupportedOutput {
+        fn_call: debug::FnActualCall,
+    },
     NoMatcherFunction {
         fn_call: debug::FnActualCall,
         pattern: debug::CallPatternDebug,
@@ -31,12 +45,28 @@ pub(crate) enum MockError {
         fn_call: debug::FnActualCall,
         actual_call_order: CallOrder,
         expected: Option<debug::CallPatternDebug>,
+        timeline: debug::Timeline,
+    },
+    #[cfg(feature = "std")]
+    CallOrderTimedOut {
+        fn_call: debug::FnActualCall,
+        timeout: core::time::Duration,
+        expected: Option<debug::CallPatternDebug>,
+        timeline: debug::Timeline,
     },
     InputsNotMatchedInCallOrder {
         fn_call: debug::FnActualCall,
         actual_call_order: CallOrder,
         pattern: debug::CallPatternDebug,
         mismatches: Mismatches,
+        timeline: debug::Timeline,
+    },
+    TagNotMatchedInCallOrder {
+        fn_call: debug::FnActualCall,
+        actual_call_order: CallOrder,
+        pattern: debug::CallPatternDebug,
+        expected_tag: Option<&'static str>,
+        timeline: debug::Timeline,
     },
     CannotReturnValueMoreThanOnce {
         fn_call: debug::FnActualCall,
@@ -57,6 +87,11 @@ pub(crate) enum MockError {
         pattern: debug::CallPatternDebug,
         msg: Box<str>,
     },
+    ArgNotWritten {
+        fn_call: debug::FnActualCall,
+        pattern: debug::CallPatternDebug,
+        error: WriteArgError,
+    },
 }
 
 impl core::fmt::Display for MockError {
@@ -68,6 +103,49 @@ impl core::fmt::Display for MockError {
             Self::NoMockImplementation { fn_call } => {
                 write!(f, "{fn_call}: No mock implementation found.")
             }
+            Self::NoMockImplementationForTypes {
+                fn_call,
+                requested,
+                mocked,
+            } => {
+                write!(
+                    f,
+                    "{fn_call}: No mock implementation found for type arguments `{requested}`. Mocked type arguments: "
+                )?;
+                for (index, type_args) in mocked.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "`{type_args}`")?;
+                }
+                write!(f, ".")
+            }
+            Self::TypeFactoryMissingType {
+                fn_call,
+                requested,
+                produced,
+            } => {
+                write!(
+                    f,
+                    "{fn_call}: The type factory cannot produce a value of type `{requested}`. Produced types: "
+                )?;
+                if produced.is_empty() {
+                    write!(f, "none")?;
+                }
+                for (index, type_name) in produced.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "`{type_name}`")?;
+                }
+                write!(f, ".")
+            }
+            Self::TypeFactoryUnsupportedOutput { fn_call } => {
+                write!(
+                    f,
+                    "{fn_call}: Type factories can only respond with owned outputs."
+                )
+            }
             Self::NoMatcherFunction { fn_call, pattern } => {
                 write!(
                     f,
@@ -97,20 +175,49 @@ impl core::fmt::Display for MockError {
                 fn_call,
                 actual_call_order,
                 expected,
+                timeline,
             } => {
                 if let Some(expected) = expected {
-                    write!(f, "{fn_call}: Method matched in wrong order. Expected a call matching {expected}.")
+                    write!(f, "{fn_call}: Method matched in wrong order. Expected a call matching {expected}.")?;
                 } else {
-                    write!(f, "{fn_call}: Ordered call ({actual_call_order}) out of range: There were no more ordered call patterns in line for selection.")
+                    write!(f, "{fn_call}: Ordered call ({actual_call_order}) out of range: There were no more ordered call patterns in line for selection.")?;
+                }
+                write!(f, "{timeline}")
+            }
+            #[cfg(feature = "std")]
+            Self::CallOrderTimedOut {
+                fn_call,
+                timeout,
+                expected,
+                timeline,
+            } => {
+                write!(f, "{fn_call}: Timed out after {timeout:?} waiting for its turn in the call order.")?;
+                if let Some(expected) = expected {
+                    write!(f, " Expected a call matching {expected}.")?;
                 }
+                write!(f, "{timeline}")
             }
             Self::InputsNotMatchedInCallOrder {
                 fn_call,
                 actual_call_order,
                 pattern,
                 mismatches,
+                timeline,
             } => {
-                write!(f, "{fn_call}: Method invoked in the correct order ({actual_call_order}), but inputs didn't match {pattern}. {mismatches}")
+                write!(f, "{fn_call}: Method invoked in the correct order ({actual_call_order}), but inputs didn't match {pattern}. {mismatches}{timeline}")
+            }
+            Self::TagNotMatchedInCallOrder {
+                fn_call,
+                actual_call_order,
+                pattern,
+                expected_tag,
+                timeline,
+            } => {
+                match expected_tag {
+                    Some(tag) => write!(f, "{fn_call}: Method invoked in the correct order ({actual_call_order}), but {pattern} expected a call on the instance tagged \"{tag}\".")?,
+                    None => write!(f, "{fn_call}: Method invoked in the correct order ({actual_call_order}), but {pattern} expected a call on an untagged instance.")?,
+                }
+                write!(f, "{timeline}")
             }
             Self::CannotReturnValueMoreThanOnce { fn_call, pattern } => {
                 write!(f, "{fn_call}: Cannot return value more than once from {pattern}, because of missing Clone bound. Try using `.each_call()` or explicitly quantifying the response.")
@@ -142,7 +249,110 @@ impl core::fmt::Display for MockError {
                 pattern,
                 msg,
             } => write!(f, "{fn_call}: Explicit panic from {pattern}: {msg}"),
+            Self::ArgNotWritten {
+                fn_call,
+                pattern,
+                error,
+            } => write!(
+                f,
+                "{fn_call}: Could not write argument of {pattern}: {error}"
+            ),
+        }
+    }
+}
+
+impl MockError {
+    /// The name of the variant, which identifies the error in the compact `defmt` encoding.
+    #[cfg(feature = "defmt-1")]
+    fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Downcast { .. } => "Downcast",
+            Self::NoMockImplementation { .. } => "NoMockImplementation",
+            Self::NoMockImplementationForTypes { .. } => "NoMockImplementationForTypes",
+            Self::TypeFactoryMissingType { .. } => "TypeFactoryMissingType",
+            Self::TypeFactoryUnsupportedOutput { .. } => "TypeFactoryUnsupportedOutput",
+            Self::NoMatcherFunction { .. } => "NoMatcherFunction",
+            Self::NoMatchingCallPatterns { .. } => "NoMatchingCallPatterns",
+            Self::NoOutputAvailableForCallPattern { .. } => "NoOutputAvailableForCallPattern",
+            Self::MockNeverCalled { .. } => "MockNeverCalled",
+            Self::CallOrderNotMatchedForMockFn { .. } => "CallOrderNotMatchedForMockFn",
+            #[cfg(feature = "std")]
+            Self::CallOrderTimedOut { .. } => "CallOrderTimedOut",
+            Self::InputsNotMatchedInCallOrder { .. } => "InputsNotMatchedInCallOrder",
+            Self::TagNotMatchedInCallOrder { .. } => "TagNotMatchedInCallOrder",
+            Self::CannotReturnValueMoreThanOnce { .. } => "CannotReturnValueMoreThanOnce",
+            Self::FailedVerification(_) => "FailedVerification",
+            Self::CannotUnmock { .. } => "CannotUnmock",
+            Self::NoDefaultImpl { .. } => "NoDefaultImpl",
+            Self::NotAnswered { .. } => "NotAnswered",
+            Self::ExplicitPanic { .. } => "ExplicitPanic",
+            Self::ArgNotWritten { .. } => "ArgNotWritten",
+        }
+    }
+
+    /// Log the error with `defmt`, where it is raised or found by verification. Does nothing without the `defmt-1` feature.
+    pub fn log(&self) {
+        #[cfg(feature = "defmt-1")]
+        defmt::error!("unimock MockError: {}", self);
+    }
+}
+
+/// A compact encoding of mock errors for logging with `defmt`: The variant of the error and the call or mock fn it is about.
+///
+/// The full message is in the panic message.
+#[cfg(feature = "defmt-1")]
+impl defmt::Format for MockError {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let variant = self.variant_name();
+
+        match self {
+            Self::Downcast { fn_call, .. }
+            | Self::NoMockImplementation { fn_call }
+            | Self::NoMockImplementationForTypes { fn_call, .. }
+            | Self::TypeFactoryMissingType { fn_call, .. }
+            | Self::TypeFactoryUnsupportedOutput { fn_call }
+            | Self::NoMatcherFunction { fn_call, .. }
+            | Self::NoMatchingCallPatterns { fn_call, .. }
+            | Self::NoOutputAvailableForCallPattern { fn_call, .. }
+            | Self::CallOrderNotMatchedForMockFn { fn_call, .. }
+            | Self::InputsNotMatchedInCallOrder { fn_call, .. }
+            | Self::TagNotMatchedInCallOrder { fn_call, .. }
+            | Self::CannotReturnValueMoreThanOnce { fn_call, .. }
+            | Self::ExplicitPanic { fn_call, .. }
+            | Self::ArgNotWritten { fn_call, .. } => {
+                defmt::write!(f, "{=str} in {}", variant, fn_call)
+            }
+            #[cfg(feature = "std")]
+            Self::CallOrderTimedOut { fn_call, .. } => {
+                defmt::write!(f, "{=str} in {}", variant, fn_call)
+            }
+            Self::MockNeverCalled { info }
+            | Self::CannotUnmock { info }
+            | Self::NoDefaultImpl { info }
+            | Self::NotAnswered { info } => {
+                defmt::write!(f, "{=str} for {}", variant, info.path)
+            }
+            Self::FailedVerification(message) => {
+                defmt::write!(f, "{=str}: {=str}", variant, message.as_str())
+            }
+        }
+    }
+}
+
+/// Names of the type arguments (and values of the const arguments) of a generic mock fn.
+#[derive(Clone)]
+pub struct TypeArgs(pub Box<[String]>);
+
+impl core::fmt::Display for TypeArgs {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "<")?;
+        for (index, type_name) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{type_name}")?;
         }
+        write!(f, ">")
     }
 }
 
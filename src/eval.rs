@@ -1,9 +1,12 @@
-use crate::alloc::{Box, String};
-use crate::call_pattern::{CallPattern, PatIndex, PatternError, PatternResult};
+use crate::alloc::{Box, String, Vec};
+use crate::call_pattern::{CallPattern, DynArgWriter, PatIndex, PatternError, PatternResult};
 use crate::error::{self};
 use crate::error::{MockError, MockResult};
+use crate::factory::{ProduceError, TypeFactory};
 use crate::fn_mocker::{FnMocker, PatternMatchMode};
+use crate::input::ArgWriter;
 use crate::mismatch::Mismatches;
+use crate::output::{GetOutput, Kind};
 use crate::private::{Continuation, Eval, MismatchReporter};
 use crate::responder::{DowncastResponder, DynResponder};
 use crate::state::SharedState;
@@ -14,68 +17,158 @@ enum EvalResult<'u> {
     Responder(EvalResponder<'u>),
     Unmock,
     CallDefaultImpl,
+    TypeFactory(&'u TypeFactory),
 }
 
 struct EvalResponder<'u> {
     fn_mocker: &'u FnMocker,
     pat_index: PatIndex,
     dyn_responder: &'u DynResponder,
+    arg_writers: &'u [DynArgWriter],
+}
+
+/// The arg writers of the matched call pattern, applied to the inputs after resolving.
+struct ArgWrites<'u, F: MockFn> {
+    fn_mocker: &'u FnMocker,
+    pat_index: PatIndex,
+    arg_writers: Vec<&'u ArgWriter<F>>,
+}
+
+/// An evaluation that is not yet attached to the inputs.
+enum Resolved<'u, F: MockFn> {
+    Return(<<<F as MockFn>::OutputKind as Kind>::Return as GetOutput>::Output<'u>),
+    Continue(Continuation<F>),
 }
 
 pub(crate) fn eval<'u, 'i, F: MockFn>(
     unimock: &'u Unimock,
-    inputs: F::Inputs<'i>,
+    mut inputs: F::Inputs<'i>,
 ) -> MockResult<Eval<'u, 'i, F>> {
+    let (resolved, arg_writes) = resolve::<F>(unimock, &inputs)?;
+
+    if let Some(arg_writes) = arg_writes {
+        for arg_writer in arg_writes.arg_writers {
+            if let Err(error) = (arg_writer.0)(&mut inputs) {
+                let dyn_ctx = DynCtx {
+                    info: F::info(),
+                    tag: unimock.tag,
+                    shared_state: &unimock.shared_state,
+                    input_debugger: &|| F::debug_inputs(&inputs),
+                };
+                return Err(MockError::ArgNotWritten {
+                    fn_call: dyn_ctx.fn_call(),
+                    pattern: arg_writes.fn_mocker.debug_pattern(arg_writes.pat_index),
+                    error,
+                });
+            }
+        }
+    }
+
+    Ok(match resolved {
+        Resolved::Return(output) => Eval::Return(output),
+        Resolved::Continue(continuation) => Eval::Continue(continuation, inputs),
+    })
+}
+
+fn resolve<'u, F: MockFn>(
+    unimock: &'u Unimock,
+    inputs: &F::Inputs<'_>,
+) -> MockResult<(Resolved<'u, F>, Option<ArgWrites<'u, F>>)> {
     let dyn_ctx = DynCtx {
         info: F::info(),
+        tag: unimock.tag,
         shared_state: &unimock.shared_state,
-        input_debugger: &|| F::debug_inputs(&inputs),
+        input_debugger: &|| F::debug_inputs(inputs),
     };
 
-    match dyn_ctx.eval_dyn(&|pattern, reporter| pattern.match_inputs::<F>(&inputs, reporter))? {
-        EvalResult::Responder(eval_responder) => match eval_responder.dyn_responder {
-            DynResponder::Return(dyn_return_responder) => {
-                match dyn_ctx
-                    .downcast_responder::<F, _>(dyn_return_responder, &eval_responder)?
-                    .get_output()
-                {
-                    Some(output) => Ok(Eval::Return(output)),
-                    None => Err(MockError::CannotReturnValueMoreThanOnce {
+    match dyn_ctx.eval_dyn(&|pattern, reporter| pattern.match_inputs::<F>(inputs, reporter))? {
+        EvalResult::Responder(eval_responder) => {
+            let arg_writers = eval_responder
+                .arg_writers
+                .iter()
+                .map(|dyn_arg_writer| {
+                    dyn_arg_writer.downcast::<F>().map_err(|err| {
+                        dyn_ctx.map_pattern_error(
+                            err,
+                            eval_responder.fn_mocker,
+                            eval_responder.pat_index,
+                        )
+                    })
+                })
+                .collect::<MockResult<Vec<_>>>()?;
+
+            let resolved = match eval_responder.dyn_responder {
+                DynResponder::Return(dyn_return_responder) => {
+                    match dyn_ctx
+                        .downcast_responder::<F, _>(dyn_return_responder, &eval_responder)?
+                        .get_output()
+                    {
+                        Some(output) => Resolved::Return(output),
+                        None => {
+                            return Err(MockError::CannotReturnValueMoreThanOnce {
+                                fn_call: dyn_ctx.fn_call(),
+                                pattern: eval_responder
+                                    .fn_mocker
+                                    .debug_pattern(eval_responder.pat_index),
+                            })
+                        }
+                    }
+                }
+                DynResponder::Answer(dyn_responder) => {
+                    let answerer =
+                        dyn_ctx.downcast_responder::<F, _>(dyn_responder, &eval_responder)?;
+                    Resolved::Continue(Continuation::Answer(answerer.answer_closure.clone()))
+                }
+                DynResponder::Panic(msg) => {
+                    return Err(MockError::ExplicitPanic {
                         fn_call: dyn_ctx.fn_call(),
                         pattern: eval_responder
                             .fn_mocker
                             .debug_pattern(eval_responder.pat_index),
-                    }),
+                        msg: msg.clone(),
+                    })
+                }
+                DynResponder::Unmock => Resolved::Continue(Continuation::Unmock),
+                DynResponder::ApplyDefaultImpl => Resolved::Continue(Continuation::CallDefaultImpl),
+            };
+
+            Ok((
+                resolved,
+                Some(ArgWrites {
+                    fn_mocker: eval_responder.fn_mocker,
+                    pat_index: eval_responder.pat_index,
+                    arg_writers,
+                }),
+            ))
+        }
+        EvalResult::TypeFactory(type_factory) => {
+            match <F::OutputKind as Kind>::produce(type_factory) {
+                Ok(output) => Ok((Resolved::Return(output), None)),
+                Err(ProduceError::MissingType(requested)) => {
+                    Err(MockError::TypeFactoryMissingType {
+                        fn_call: dyn_ctx.fn_call(),
+                        requested,
+                        produced: type_factory.type_names().collect(),
+                    })
+                }
+                Err(ProduceError::UnsupportedOutput) => {
+                    Err(MockError::TypeFactoryUnsupportedOutput {
+                        fn_call: dyn_ctx.fn_call(),
+                    })
                 }
             }
-            DynResponder::Answer(dyn_responder) => {
-                let answerer =
-                    dyn_ctx.downcast_responder::<F, _>(dyn_responder, &eval_responder)?;
-                Ok(Eval::Continue(
-                    Continuation::Answer(answerer.answer_closure.clone()),
-                    inputs,
-                ))
-            }
-            DynResponder::Panic(msg) => Err(MockError::ExplicitPanic {
-                fn_call: dyn_ctx.fn_call(),
-                pattern: eval_responder
-                    .fn_mocker
-                    .debug_pattern(eval_responder.pat_index),
-                msg: msg.clone(),
-            }),
-            DynResponder::Unmock => Ok(Eval::Continue(Continuation::Unmock, inputs)),
-            DynResponder::ApplyDefaultImpl => {
-                Ok(Eval::Continue(Continuation::CallDefaultImpl, inputs))
-            }
-        },
-        EvalResult::Unmock => Ok(Eval::Continue(Continuation::Unmock, inputs)),
-        EvalResult::CallDefaultImpl => Ok(Eval::Continue(Continuation::CallDefaultImpl, inputs)),
+        }
+        EvalResult::Unmock => Ok((Resolved::Continue(Continuation::Unmock), None)),
+        EvalResult::CallDefaultImpl => {
+            Ok((Resolved::Continue(Continuation::CallDefaultImpl), None))
+        }
     }
 }
 
 /// 'u = unimock instance, 's = stack
 struct DynCtx<'u, 's> {
     info: MockFnInfo,
+    tag: Option<&'static str>,
     shared_state: &'u SharedState,
     input_debugger: &'s dyn Fn() -> Box<[Option<String>]>,
 }
@@ -86,31 +179,46 @@ impl<'u, 's> DynCtx<'u, 's> {
         &self,
         match_inputs: &dyn Fn(&CallPattern, Option<&mut MismatchReporter>) -> PatternResult<bool>,
     ) -> MockResult<EvalResult<'u>> {
-        let fn_mocker = match self.shared_state.fn_mockers.get(&self.info.type_id) {
-            None => {
-                return if self.info.has_default_impl {
-                    Ok(EvalResult::CallDefaultImpl)
-                } else if self.info.partial_by_default {
-                    Ok(EvalResult::Unmock)
-                } else {
-                    match self.shared_state.fallback_mode {
-                        FallbackMode::Error => Err(MockError::NoMockImplementation {
-                            fn_call: self.fn_call(),
-                        }),
-                        FallbackMode::Unmock => Ok(EvalResult::Unmock),
-                    }
-                }
+        let fn_mockers = self.shared_state.fn_mockers_for(self.info.type_id);
+        let Some(first_fn_mocker) = fn_mockers.clone().next() else {
+            if let Some(type_factory) = self
+                .info
+                .generic
+                .and_then(|generic| self.shared_state.type_factory(generic.untyped_type_id))
+            {
+                type_factory
+                    .calls
+                    .fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+                return Ok(EvalResult::TypeFactory(type_factory));
             }
-            Some(fn_mocker) => fn_mocker,
+
+            return if self.info.has_default_impl {
+                Ok(EvalResult::CallDefaultImpl)
+            } else if self.info.partial_by_default {
+                Ok(EvalResult::Unmock)
+            } else {
+                match self.shared_state.fallback_mode {
+                    FallbackMode::Error => Err(self.no_mock_implementation()),
+                    FallbackMode::Unmock => Ok(EvalResult::Unmock),
+                }
+            };
         };
 
-        match self.match_call_pattern(fn_mocker, match_inputs)? {
-            Some((pat_index, pattern)) => match pattern.next_responder() {
-                Some(dyn_responder) => Ok(EvalResult::Responder(EvalResponder {
-                    fn_mocker,
-                    pat_index,
-                    dyn_responder,
-                })),
+        match self.match_call_pattern(
+            first_fn_mocker.pattern_match_mode,
+            fn_mockers.clone(),
+            match_inputs,
+        )? {
+            Some((fn_mocker, pat_index, pattern)) => match pattern.next_responder() {
+                Some(call_order_responder) => {
+                    self.shared_state.call_notifier.notify();
+                    Ok(EvalResult::Responder(EvalResponder {
+                        fn_mocker,
+                        pat_index,
+                        dyn_responder: &call_order_responder.responder,
+                        arg_writers: &call_order_responder.arg_writers,
+                    }))
+                }
                 None => Err(MockError::NoOutputAvailableForCallPattern {
                     fn_call: self.fn_call(),
                     pattern: fn_mocker.debug_pattern(pat_index),
@@ -118,10 +226,21 @@ impl<'u, 's> DynCtx<'u, 's> {
             },
             None => match self.shared_state.fallback_mode {
                 FallbackMode::Error => {
+                    let inputs_debug = self.debug_inputs();
                     let mut builder = Mismatches::builder();
-                    for (pat_index, call_pattern) in fn_mocker.call_patterns.iter().enumerate() {
+                    // Numbered across the layers, so that patterns of different layers are told apart
+                    let call_patterns = fn_mockers
+                        .flat_map(|fn_mocker| fn_mocker.call_patterns.iter())
+                        .enumerate();
+                    for (pat_index, call_pattern) in call_patterns {
+                        if call_pattern.input_matcher.tag != self.tag
+                            || call_pattern.call_counter.is_retired()
+                        {
+                            continue;
+                        }
                         let mut mismatch_reporter = MismatchReporter::new_enabled();
                         let _ = match_inputs(call_pattern, Some(&mut mismatch_reporter));
+                        mismatch_reporter.fill_actual(&inputs_debug);
                         builder.collect_from_reporter(PatIndex(pat_index), mismatch_reporter);
                     }
 
@@ -135,44 +254,153 @@ impl<'u, 's> DynCtx<'u, 's> {
         }
     }
 
+    fn no_mock_implementation(&self) -> MockError {
+        let Some(generic) = self.info.generic else {
+            return MockError::NoMockImplementation {
+                fn_call: self.fn_call(),
+            };
+        };
+
+        let mocked: Vec<_> = self
+            .shared_state
+            .fn_mockers()
+            .filter_map(|fn_mocker| fn_mocker.info.generic)
+            .filter(|mocked| mocked.untyped_type_id == generic.untyped_type_id)
+            .map(|mocked| error::TypeArgs((mocked.type_args)().into()))
+            .collect();
+
+        if mocked.is_empty() {
+            MockError::NoMockImplementation {
+                fn_call: self.fn_call(),
+            }
+        } else {
+            MockError::NoMockImplementationForTypes {
+                fn_call: self.fn_call(),
+                requested: error::TypeArgs((generic.type_args)().into()),
+                mocked: mocked.into(),
+            }
+        }
+    }
+
+    /// Claim the next position in the call order.
+    ///
+    /// With a call order timeout, this waits until the call is the one expected next.
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    fn next_ordered_call_index(
+        &self,
+        fn_mockers: impl Iterator<Item = &'u FnMocker> + Clone,
+        match_inputs: &dyn Fn(&CallPattern, Option<&mut MismatchReporter>) -> PatternResult<bool>,
+    ) -> MockResult<usize> {
+        #[cfg(feature = "std")]
+        if let Some(timeout) = self.shared_state.call_order_timeout {
+            use crate::wait::Turn;
+
+            return crate::wait::wait_for_turn(
+                self.shared_state,
+                timeout,
+                |index| match fn_mockers
+                    .clone()
+                    .find_map(|fn_mocker| fn_mocker.find_call_pattern_for_call_order(index))
+                {
+                    Some((_, pattern))
+                        if pattern.input_matcher.tag == self.tag
+                            && matches!(match_inputs(pattern, None), Ok(true)) =>
+                    {
+                        Turn::Mine
+                    }
+                    _ if self.shared_state.has_ordered_call_pattern(index) => Turn::NotYet,
+                    _ => Turn::Never,
+                },
+            )
+            .ok_or_else(|| {
+                let index = self.shared_state.peek_ordered_call_index();
+                MockError::CallOrderTimedOut {
+                    fn_call: self.fn_call(),
+                    timeout,
+                    expected: self
+                        .shared_state
+                        .find_ordered_expected_call_pattern_debug(index),
+                    timeline: self.shared_state.timeline(),
+                }
+            });
+        }
+
+        Ok(self.shared_state.bump_ordered_call_index())
+    }
+
+    /// Find the call pattern matching the call, among the mockers of all layers.
+    ///
+    /// Unordered call patterns of newer layers take precedence over those of older layers.
+    /// Call patterns with an exact call count that was verified by a checkpoint are skipped.
     fn match_call_pattern(
         &self,
-        fn_mocker: &'u FnMocker,
+        pattern_match_mode: PatternMatchMode,
+        mut fn_mockers: impl Iterator<Item = &'u FnMocker> + Clone,
         match_inputs: &dyn Fn(&CallPattern, Option<&mut MismatchReporter>) -> PatternResult<bool>,
-    ) -> MockResult<Option<(PatIndex, &'u CallPattern)>> {
-        match fn_mocker.pattern_match_mode {
-            PatternMatchMode::InAnyOrder => fn_mocker
-                .call_patterns
-                .iter()
-                .enumerate()
-                .filter_map(
-                    |(pat_index, call_pattern)| match match_inputs(call_pattern, None) {
-                        Ok(false) => None,
-                        Ok(true) => Some(Ok((PatIndex(pat_index), call_pattern))),
-                        Err(err) => Some(Err((PatIndex(pat_index), err))),
-                    },
-                )
-                .next()
+    ) -> MockResult<Option<(&'u FnMocker, PatIndex, &'u CallPattern)>> {
+        match pattern_match_mode {
+            PatternMatchMode::InAnyOrder => fn_mockers
+                .filter_map(|fn_mocker| {
+                    fn_mocker
+                        .call_patterns
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, call_pattern)| {
+                            call_pattern.input_matcher.tag == self.tag
+                                && !call_pattern.call_counter.is_retired()
+                        })
+                        .find_map(|(pat_index, call_pattern)| {
+                            match match_inputs(call_pattern, None) {
+                                Ok(false) => None,
+                                Ok(true) => {
+                                    Some(Ok((fn_mocker, PatIndex(pat_index), call_pattern)))
+                                }
+                                Err(err) => Some(Err((fn_mocker, PatIndex(pat_index), err))),
+                            }
+                        })
+                })
+                .last()
                 .transpose()
-                .map_err(|(pat_index, err)| self.map_pattern_error(err, fn_mocker, pat_index)),
+                .map_err(|(fn_mocker, pat_index, err)| {
+                    self.map_pattern_error(err, fn_mocker, pat_index)
+                }),
             PatternMatchMode::InOrder => {
-                let ordered_call_index = self.shared_state.bump_ordered_call_index();
+                let ordered_call_index =
+                    self.next_ordered_call_index(fn_mockers.clone(), match_inputs)?;
+                self.shared_state
+                    .record_ordered_call(ordered_call_index, self.fn_call());
 
-                let (pat_index, pattern) = fn_mocker
-                    .find_call_pattern_for_call_order(ordered_call_index)
+                let (fn_mocker, pat_index, pattern) = fn_mockers
+                    .find_map(|fn_mocker| {
+                        let (pat_index, pattern) =
+                            fn_mocker.find_call_pattern_for_call_order(ordered_call_index)?;
+                        Some((fn_mocker, pat_index, pattern))
+                    })
                     .ok_or_else(|| MockError::CallOrderNotMatchedForMockFn {
                         fn_call: self.fn_call(),
                         actual_call_order: error::CallOrder(ordered_call_index),
                         expected: self
                             .shared_state
                             .find_ordered_expected_call_pattern_debug(ordered_call_index),
+                        timeline: self.shared_state.timeline(),
                     })?;
 
+                if pattern.input_matcher.tag != self.tag {
+                    return Err(MockError::TagNotMatchedInCallOrder {
+                        fn_call: self.fn_call(),
+                        actual_call_order: error::CallOrder(ordered_call_index),
+                        pattern: fn_mocker.debug_pattern(pat_index),
+                        expected_tag: pattern.input_matcher.tag,
+                        timeline: self.shared_state.timeline(),
+                    });
+                }
+
                 let mut mismatch_reporter = MismatchReporter::new_enabled();
 
                 if !match_inputs(pattern, Some(&mut mismatch_reporter))
                     .map_err(|err| self.map_pattern_error(err, fn_mocker, pat_index))?
                 {
+                    mismatch_reporter.fill_actual(&self.debug_inputs());
                     let mut builder = Mismatches::builder();
                     builder.collect_from_reporter(pat_index, mismatch_reporter);
 
@@ -181,10 +409,11 @@ impl<'u, 's> DynCtx<'u, 's> {
                         actual_call_order: error::CallOrder(ordered_call_index),
                         pattern: fn_mocker.debug_pattern(pat_index),
                         mismatches: builder.build(),
+                        timeline: self.shared_state.timeline(),
                     });
                 }
 
-                Ok(Some((pat_index, pattern)))
+                Ok(Some((fn_mocker, pat_index, pattern)))
             }
         }
     }
@@ -226,6 +455,7 @@ impl<'u, 's> DynCtx<'u, 's> {
         debug::FnActualCall {
             info: self.info,
             inputs_debug: self.debug_inputs(),
+            caller: self.shared_state.current_caller(self.tag),
         }
     }
 
@@ -0,0 +1,122 @@
+//! Producing values of types that are not known until a generic method gets called.
+
+use core::any::{Any, TypeId};
+use core::sync::atomic::AtomicUsize;
+
+use crate::alloc::{Box, String, Vec};
+use crate::{clause, Clause};
+
+/// A factory of values for mocking methods with a generic return type, like `fn get<T>(&self) -> T`.
+///
+/// Values are produced by type, so one factory can respond to any instantiation of the generic parameters.
+/// It is registered with the `with_any_types` method found on generic mock entries.
+///
+/// Type factories only apply to methods with owned outputs.
+///
+/// # Example
+/// ```rust
+/// # use unimock::*;
+/// # use unimock::factory::TypeFactory;
+/// #[unimock(api=ConfigMock)]
+/// trait Config {
+///     fn get<T: 'static>(&self, key: &str) -> T;
+/// }
+///
+/// let u = Unimock::new(
+///     ConfigMock::get.with_any_types(
+///         TypeFactory::new()
+///             .with(|| String::from("value"))
+///             .default_of::<i32>(),
+///     ),
+/// );
+///
+/// assert_eq!("value", u.get::<String>("a"));
+/// assert_eq!(0, u.get::<i32>("b"));
+/// ```
+#[derive(Default)]
+pub struct TypeFactory {
+    producers: Vec<Producer>,
+    pub(crate) calls: AtomicUsize,
+}
+
+struct Producer {
+    type_id: TypeId,
+    type_name: &'static str,
+    produce: Box<dyn Fn() -> Box<dyn Any> + Send + Sync>,
+}
+
+impl TypeFactory {
+    /// Create an empty type factory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Produce values of type `T` using its [Default] implementation.
+    pub fn default_of<T: Default + 'static>(self) -> Self {
+        self.with(T::default)
+    }
+
+    /// Produce values of type `T` by calling the given function.
+    ///
+    /// A later registration for the same type replaces the earlier one.
+    pub fn with<T: 'static>(mut self, produce: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        self.producers
+            .retain(|producer| producer.type_id != TypeId::of::<T>());
+        self.producers.push(Producer {
+            type_id: TypeId::of::<T>(),
+            type_name: core::any::type_name::<T>(),
+            produce: Box::new(move || -> Box<dyn Any> { Box::new(produce()) }),
+        });
+        self
+    }
+
+    pub(crate) fn produce<T: 'static>(&self) -> Result<T, ProduceError> {
+        let producer = self
+            .producers
+            .iter()
+            .find(|producer| producer.type_id == TypeId::of::<T>())
+            .ok_or(ProduceError::MissingType(core::any::type_name::<T>()))?;
+
+        match (producer.produce)().downcast::<T>() {
+            Ok(value) => Ok(*value),
+            Err(_) => Err(ProduceError::MissingType(core::any::type_name::<T>())),
+        }
+    }
+
+    pub(crate) fn type_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.producers.iter().map(|producer| producer.type_name)
+    }
+}
+
+/// Error produced when a [TypeFactory] cannot respond to a call.
+#[doc(hidden)]
+pub enum ProduceError {
+    /// The factory has no producer for the requested type.
+    MissingType(&'static str),
+    /// The output kind of the method does not support type factories.
+    UnsupportedOutput,
+}
+
+/// A [Clause] registering a [TypeFactory] for every instantiation of a generic mock entry.
+pub struct AnyTypes {
+    untyped_type_id: TypeId,
+    path: &'static [&'static str; 2],
+    factory: TypeFactory,
+}
+
+impl AnyTypes {
+    #[doc(hidden)]
+    pub fn new<U: 'static>(path: &'static [&'static str; 2], factory: TypeFactory) -> Self {
+        Self {
+            untyped_type_id: TypeId::of::<U>(),
+            path,
+            factory,
+        }
+    }
+}
+
+impl Clause for AnyTypes {
+    fn deconstruct(self, sink: &mut dyn clause::term::Sink) -> Result<(), String> {
+        sink.push_type_factory(self.untyped_type_id, self.path, self.factory)
+    }
+}
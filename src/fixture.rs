@@ -0,0 +1,214 @@
+use core::any::TypeId;
+
+use crate::alloc::{Arc, BTreeMap, BTreeSet, String, Vec};
+use crate::build::dyn_builder::DynCallPatternBuilder;
+use crate::clause::term::Sink;
+use crate::factory::TypeFactory;
+use crate::fn_mocker::PatternMatchMode;
+use crate::{Clause, MockFnInfo};
+
+/// A reusable, cloneable set of clauses, for setups shared between tests.
+///
+/// A fixture is created from a function that produces its clause, and is itself a [Clause] that can be passed to [Unimock::new](crate::Unimock::new) any number of times.
+/// Mock fns that are only set up by fixtures are not reported as dead mocks when they are never called,
+/// but call patterns with an explicit call count, like `.once()`, are still verified.
+///
+/// [Fixture::override_with] makes a new fixture whose call patterns take precedence over the fixture's call patterns for the same [MockFn](crate::MockFn).
+///
+/// # Example
+/// ```rust
+/// # use unimock::*;
+/// #[unimock(api=ConfigMock)]
+/// trait Config {
+///     fn get(&self, key: &str) -> Option<String>;
+///     fn is_enabled(&self, feature: &str) -> bool;
+/// }
+///
+/// let base = Fixture::new(|| {
+///     (
+///         ConfigMock::get.each_call(matching!(_)).returns(None),
+///         ConfigMock::is_enabled.each_call(matching!(_)).returns(false),
+///     )
+/// });
+///
+/// let u = Unimock::new(base.clone());
+/// assert!(!u.is_enabled("beta"));
+///
+/// let beta = base.override_with(|| {
+///     ConfigMock::is_enabled.each_call(matching!("beta")).returns(true)
+/// });
+///
+/// let u = Unimock::new(beta.clone());
+/// assert!(u.is_enabled("beta"));
+/// assert!(!u.is_enabled("gamma"));
+///
+/// let u = Unimock::new(beta.override_with(|| {
+///     ConfigMock::get.each_call(matching!("channel")).returns(Some("beta".to_string()))
+/// }));
+/// assert_eq!(Some("beta".to_string()), u.get("channel"));
+/// assert!(u.is_enabled("beta"));
+/// ```
+#[derive(Clone)]
+pub struct Fixture {
+    deconstruct: Arc<DeconstructFn>,
+}
+
+type DeconstructFn = dyn Fn(&mut dyn Sink) -> Result<(), String> + Send + Sync;
+
+impl Fixture {
+    /// Create a fixture from a function producing its clause. The function is called each time the fixture is used.
+    pub fn new<C: Clause>(clause_fn: impl Fn() -> C + Send + Sync + 'static) -> Self {
+        Self {
+            deconstruct: Arc::new(move |sink| {
+                let mut recorder = Recorder::default();
+                clause_fn().deconstruct(&mut recorder)?;
+                for push in recorder.0 {
+                    push.mark_from_fixture().forward(sink)?;
+                }
+                Ok(())
+            }),
+        }
+    }
+
+    /// Make a new fixture from this one, where the call patterns of the clause produced by `clause_fn` take precedence over this fixture's call patterns for the same [MockFn](crate::MockFn).
+    ///
+    /// The overriding call patterns are tried first, and this fixture's call patterns for the same [MockFn](crate::MockFn) remain as fallbacks
+    /// only when both are set up with `each_call` or `some_call`. Otherwise, this fixture's call patterns for that [MockFn](crate::MockFn) are replaced.
+    /// Type factories in the clause replace this fixture's type factories for the same method.
+    ///
+    /// Unlike the call patterns of this fixture, overriding call patterns that are never called are reported as dead mocks.
+    pub fn override_with<C: Clause>(
+        &self,
+        clause_fn: impl Fn() -> C + Send + Sync + 'static,
+    ) -> Fixture {
+        let fixture = self.clone();
+        Self {
+            deconstruct: Arc::new(move |sink| override_fixture(&fixture, clause_fn(), sink)),
+        }
+    }
+
+    fn record(&self) -> Result<Recorder, String> {
+        let mut recorder = Recorder::default();
+        (self.deconstruct)(&mut recorder)?;
+        Ok(recorder)
+    }
+}
+
+impl Clause for Fixture {
+    fn deconstruct(self, sink: &mut dyn Sink) -> Result<(), String> {
+        (self.deconstruct)(sink)
+    }
+}
+
+fn override_fixture(
+    fixture: &Fixture,
+    clause: impl Clause,
+    sink: &mut dyn Sink,
+) -> Result<(), String> {
+    let mut overrides = Recorder::default();
+    clause.deconstruct(&mut overrides)?;
+
+    let mut overridden_modes: BTreeMap<TypeId, PatternMatchMode> = BTreeMap::new();
+    let mut overridden_factories: BTreeSet<TypeId> = BTreeSet::new();
+    for push in overrides.0.iter() {
+        match push {
+            Push::Pattern(info, builder) => {
+                overridden_modes
+                    .entry(info.type_id)
+                    .or_insert(builder.pattern_match_mode);
+            }
+            Push::TypeFactory(untyped_type_id, ..) => {
+                overridden_factories.insert(*untyped_type_id);
+            }
+        }
+    }
+
+    let mut pending = overrides.0;
+
+    // The overriding call patterns are placed where the fixture first set up the same MockFn,
+    // so that ordered call patterns keep their position in the call order.
+    for push in fixture.record()?.0 {
+        match &push {
+            Push::Pattern(info, builder) => {
+                if let Some(override_mode) = overridden_modes.get(&info.type_id) {
+                    let (overriding, rest): (Vec<_>, Vec<_>) = core::mem::take(&mut pending)
+                        .into_iter()
+                        .partition(|pending_push| pending_push.is_pattern_for(info.type_id));
+                    pending = rest;
+
+                    for overriding_push in overriding {
+                        overriding_push.forward(sink)?;
+                    }
+
+                    let is_fallback = *override_mode == PatternMatchMode::InAnyOrder
+                        && builder.pattern_match_mode == PatternMatchMode::InAnyOrder;
+                    if !is_fallback {
+                        continue;
+                    }
+                }
+            }
+            Push::TypeFactory(untyped_type_id, ..) => {
+                if overridden_factories.contains(untyped_type_id) {
+                    continue;
+                }
+            }
+        }
+
+        push.forward(sink)?;
+    }
+
+    for push in pending {
+        push.forward(sink)?;
+    }
+
+    Ok(())
+}
+
+enum Push {
+    Pattern(MockFnInfo, DynCallPatternBuilder),
+    TypeFactory(TypeId, &'static [&'static str; 2], TypeFactory),
+}
+
+impl Push {
+    fn is_pattern_for(&self, type_id: TypeId) -> bool {
+        matches!(self, Self::Pattern(info, _) if info.type_id == type_id)
+    }
+
+    fn mark_from_fixture(mut self) -> Self {
+        if let Self::Pattern(_, builder) = &mut self {
+            builder.from_fixture = true;
+        }
+        self
+    }
+
+    fn forward(self, sink: &mut dyn Sink) -> Result<(), String> {
+        match self {
+            Self::Pattern(info, builder) => sink.push(info, builder),
+            Self::TypeFactory(untyped_type_id, path, factory) => {
+                sink.push_type_factory(untyped_type_id, path, factory)
+            }
+        }
+    }
+}
+
+/// Records the terms of a clause, for forwarding them later.
+#[derive(Default)]
+struct Recorder(Vec<Push>);
+
+impl Sink for Recorder {
+    fn push(&mut self, info: MockFnInfo, builder: DynCallPatternBuilder) -> Result<(), String> {
+        self.0.push(Push::Pattern(info, builder));
+        Ok(())
+    }
+
+    fn push_type_factory(
+        &mut self,
+        untyped_type_id: TypeId,
+        path: &'static [&'static str; 2],
+        factory: TypeFactory,
+    ) -> Result<(), String> {
+        self.0
+            .push(Push::TypeFactory(untyped_type_id, path, factory));
+        Ok(())
+    }
+}
@@ -43,7 +43,35 @@ impl FnMocker {
         )
     }
 
-    pub fn verify(&self, errors: &mut Vec<MockError>) {
+    pub fn actual_calls(&self) -> usize {
+        self.call_patterns
+            .iter()
+            .map(|pattern| pattern.call_counter.actual_count())
+            .sum()
+    }
+
+    /// End the epoch of every call pattern, after a checkpoint has verified them.
+    pub fn end_epoch(&self) {
+        for pattern in &self.call_patterns {
+            pattern.call_counter.end_epoch();
+        }
+    }
+
+    /// Verify the call counts of the call patterns in their current epoch.
+    ///
+    /// Mock fns are reported as never called by their total calls, across epochs.
+    pub fn verify(&self, policy: &Policy, errors: &mut Vec<MockError>) {
+        // Mock fns only set up by fixtures are verified as if the policy allowed unused mocks
+        let only_fixture_patterns = self
+            .call_patterns
+            .iter()
+            .all(|pattern| pattern.from_fixture);
+        let policy = if only_fixture_patterns {
+            policy.allow_unused()
+        } else {
+            *policy
+        };
+
         let mut total_calls = 0;
 
         for (pat_index, pattern) in self.call_patterns.iter().enumerate() {
@@ -52,12 +80,13 @@ impl FnMocker {
                 .verify(
                     &self.info,
                     || self.debug_pattern(PatIndex(pat_index)),
+                    &policy,
                     errors,
                 )
                 .0;
         }
 
-        if total_calls == 0 {
+        if total_calls == 0 && !policy.allow_unused {
             errors.push(error::MockError::MockNeverCalled { info: self.info });
         }
     }
@@ -0,0 +1,106 @@
+//! Traits for modelling the inputs of MockFns, used with `writes_arg`, and formatters for inputs.
+
+use crate::alloc::{format, Box, String};
+use crate::MockFn;
+
+/// Access to a mutable reference parameter of a [MockFn], selected by its parameter index.
+///
+/// The index `N` counts the parameters of the mocked method, excluding the `self` receiver.
+///
+/// This trait is implemented by the [unimock](crate::unimock) macro for every parameter of the form `&mut T`.
+pub trait MutArg<const N: usize>: MockFn {
+    /// The type behind the mutable reference.
+    type Target: ?Sized;
+
+    /// Borrow the mutable reference parameter out of the inputs.
+    fn mut_arg<'a, 'i>(inputs: &'a mut Self::Inputs<'i>) -> &'a mut Self::Target;
+}
+
+/// A value that can be written into a mutable reference parameter of type `T`.
+pub trait WriteArg<T: ?Sized> {
+    /// Write this value into the target.
+    ///
+    /// An error fails the call with a mock error.
+    fn write_arg(&self, target: &mut T) -> Result<(), WriteArgError>;
+}
+
+/// Any [Clone] value can be written by overwriting the target.
+impl<T: Clone> WriteArg<T> for T {
+    fn write_arg(&self, target: &mut T) -> Result<(), WriteArgError> {
+        target.clone_from(self);
+        Ok(())
+    }
+}
+
+/// Slices are written by copying elements into the start of the target slice.
+///
+/// Elements past the length of the value are left untouched.
+/// A value that is longer than the target slice is an error.
+impl<T: Clone, S: AsRef<[T]>> WriteArg<[T]> for S {
+    fn write_arg(&self, target: &mut [T]) -> Result<(), WriteArgError> {
+        let source = self.as_ref();
+        if source.len() > target.len() {
+            return Err(WriteArgError {
+                len: source.len(),
+                target_len: target.len(),
+            });
+        }
+        target[..source.len()].clone_from_slice(source);
+        Ok(())
+    }
+}
+
+/// A value that does not fit into the mutable reference parameter it was written to.
+#[derive(Clone, Debug)]
+pub struct WriteArgError {
+    /// The number of elements in the value.
+    pub len: usize,
+    /// The number of elements the parameter has room for.
+    pub target_len: usize,
+}
+
+impl core::fmt::Display for WriteArgError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The value has {} elements, but the parameter only has room for {}.",
+            self.len, self.target_len
+        )
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) struct ArgWriter<F: MockFn>(
+    pub Box<dyn for<'i> Fn(&mut F::Inputs<'i>) -> Result<(), WriteArgError> + Send + Sync>,
+);
+
+impl<F: MockFn> ArgWriter<F> {
+    pub fn new<const N: usize, W>(value: W) -> Self
+    where
+        F: MutArg<N>,
+        W: WriteArg<<F as MutArg<N>>::Target> + Send + Sync + 'static,
+    {
+        Self(Box::new(move |inputs: &mut F::Inputs<'_>| {
+            value.write_arg(<F as MutArg<N>>::mut_arg(inputs))
+        }))
+    }
+}
+
+/// Summarize a value by its type name and size, for inputs that do not implement [Debug](core::fmt::Debug).
+///
+/// Can be used as a formatter with `#[unimock(debug_inputs = unimock::input::type_summary)]`.
+///
+/// # Example
+/// ```rust
+/// struct Packet([u8; 4]);
+///
+/// let summary = unimock::input::type_summary(&Packet([0; 4]));
+/// assert!(summary.ends_with("Packet, 4 bytes>"));
+/// ```
+pub fn type_summary<T: ?Sized>(value: &T) -> String {
+    format!(
+        "<{}, {} bytes>",
+        core::any::type_name::<T>(),
+        core::mem::size_of_val(value)
+    )
+}
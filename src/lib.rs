@@ -114,6 +114,28 @@
 //!
 //! The argument to `answers` is a function with the same signature as the method it mocks, including the `self` parameter.
 //!
+//! When a `&mut` parameter just needs to be overwritten with a known value, [`writes_arg`](crate::build::DefineResponse::writes_arg) is more convenient.
+//! It selects the parameter by index (not counting `self`), and can be combined with any other response:
+//!
+//! ```rust
+//! # use unimock::*;
+//! #[unimock(api=ReadMock)]
+//! trait Read {
+//!     fn read(&self, buf: &mut [u8]) -> usize;
+//! }
+//!
+//! let mocked = Unimock::new(
+//!     ReadMock::read
+//!         .next_call(matching!(_))
+//!         .writes_arg::<0>(b"hi")
+//!         .returns(2_usize)
+//! );
+//!
+//! let mut buf = [0; 2];
+//! assert_eq!(2, mocked.read(&mut buf));
+//! assert_eq!(b"hi", &buf);
+//! ```
+//!
 //! ## Combining setup clauses
 //! `Unimock::new()` accepts as argument anything that implements [Clause].
 //! Basic setup clauses can be combined into composite clauses by using _tuples_:
@@ -313,6 +335,9 @@
 //! The `critical-section` feature is also required for `no_std`.
 //! These two features will likely merge into one in some future breaking release.
 //!
+//! With the `defmt-1` feature, mock errors are also logged with [defmt](https://docs.rs/defmt) before panicking, in a compact encoding of the kind of error and the call,
+//! so that test failures on embedded targets can be read from RTT-style logging. The test binary must provide a `defmt` global logger.
+//!
 //!
 //! ## Mock APIs for central crates
 //! Unimock works well when the trait being abstracted over is defined in the same code base as the once that contains the test.
@@ -327,7 +352,9 @@
 //!
 //! #### What kinds of things can be mocked with unimock?
 //! * Traits with any number of methods
-//! * Traits with generic parameters, although these cannot be lifetime constrained (i.e. need to satisfy `T: 'static`).
+//! * Traits with generic parameters. Type parameters still need to satisfy `T: 'static`, because each instantiation of a generic mock fn is told apart by its `TypeId`.
+//! * Traits and methods with const generic parameters. Like type parameters, these are selected with `with_types`, e.g. `BufferMock::read.with_types::<4>()`.
+//! * Traits with lifetime parameters. The `'static` requirement above applies to the type parameters, not to the trait's lifetime parameters.
 //! * Traits with associated types and constants, using `#[unimock(type T = Foo; const FOO: T = value;)]` syntax.
 //! * Methods with any self receiver (`self`, `&self`, `&mut self` or arbitrary (e.g. `self: Rc<Self>`)).
 //! * Methods that take reference inputs.
@@ -337,8 +364,14 @@
 //! * Methods returning any tuple combination of self-borrowed or owned elements up to 4 elements.
 //! * Methods returning a type containing lifetime parameters. For a mocked return they will have to be `'static`.
 //! * Generic methods using either explicit generic params or argument-position `impl Trait`.
+//!   Methods with a generic return type can respond to any type argument using a [`TypeFactory`](factory::TypeFactory).
 //! * Methods that are `async` or return `impl Future`.
+//! * Methods returning other kinds of `impl Trait`, as long as the trait is object safe and implemented for `Box<dyn Trait>` (e.g. `impl Iterator<Item = T>`).
+//!   These outputs are boxed as trait objects, so any `Send + Sync` value implementing the trait can be passed to `returns`.
+//!   The trait object has the same auto traits, like `Send`, as the `impl Trait`, and at most one trait that is not an auto trait.
+//! * Methods returning `Box<dyn Iterator<Item = T>>` or `Pin<Box<dyn Stream<Item = T>>>`, which can also be responded to with [`returns_items`](build::DefineResponse::returns_items).
 //! * `async_trait`-annotated traits.
+//! * Inherent `impl` blocks of structs, through a generated newtype that substitutes the struct in tests.
 //!
 //! #### What kinds of traits or methods cannot be mocked?
 //! * Static methods, i.e. no `self` receiver. Static methods with a _default body_ are accepted though, but not mockable.
@@ -433,6 +466,10 @@ compile_error!("At least one of the features `std` or `critical-section` must be
 #[cfg(feature = "std")]
 extern crate std;
 
+// The defmt macros refer to the crate as `defmt`.
+#[cfg(feature = "defmt-1")]
+extern crate defmt_1 as defmt;
+
 #[doc(hidden)]
 pub mod alloc {
     extern crate alloc;
@@ -453,6 +490,15 @@ pub mod alloc {
 /// Builder pattern types used for defining mocked behaviour.
 pub mod build;
 
+/// Reporting which mocked methods a test exercised.
+pub mod coverage;
+
+/// Values for mocking methods with generic return types.
+pub mod factory;
+
+/// Function inputs.
+pub mod input;
+
 /// Function outputs.
 pub mod output;
 
@@ -483,14 +529,20 @@ mod assemble;
 mod call_pattern;
 mod clause;
 mod counter;
+mod current;
 mod debug;
 mod error;
 mod eval;
+mod fixture;
 mod fn_mocker;
 mod mismatch;
+mod policy;
 mod responder;
 mod state;
 mod teardown;
+mod wait;
+#[cfg(feature = "std")]
+mod verifier;
 
 use core::any::Any;
 use core::any::TypeId;
@@ -594,20 +646,157 @@ use private::{DefaultImplDelegator, Matching};
 /// );
 /// ```
 ///
+/// # Inherent impl blocks
+/// The attribute can also be put on an inherent `impl` block of a struct.
+/// Its methods are extracted into a hidden trait, which is mocked as usual.
+/// The macro also generates a newtype `Unimock<Type>` wrapping a [Unimock], which has the same methods as inherent methods.
+/// `Self` in the signatures refers to the newtype, so e.g. a mocked constructor returns `Unimock<Type>`.
+/// Associated functions without a `self` receiver are mocked by the instance passed to [Unimock::enter].
+///
+/// The mock and the newtype only exist under `cfg(test)`.
+/// Code that uses the struct can instead name the generated alias `Mockable<Type>`, which is the struct itself outside of tests,
+/// and the newtype under test:
+///
+/// ```rust
+/// # use unimock::*;
+/// pub struct Client;
+///
+/// #[unimock(api=ClientMock)]
+/// impl Client {
+///     pub fn get(&self, path: &str) -> String {
+///         unimplemented!()
+///     }
+/// }
+///
+/// mod service {
+///     use super::MockableClient as Client;
+///
+///     pub fn fetch(client: &Client) -> String {
+///         client.get("/data")
+///     }
+/// }
+///
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///     use unimock::*;
+///
+///     #[test]
+///     fn fetch() {
+///         let client = UnimockClient::from(Unimock::new(
+///             ClientMock::get.next_call(matching!("/data")).returns("data"),
+///         ));
+///         assert_eq!("data", service::fetch(&client));
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Generic impl blocks and impl blocks of traits are not supported.
 ///
 /// # Arguments
 /// The unimock macro accepts a number of comma or colon-separated key-value configuration parameters:
 ///
 /// * `#[unimock(api=#ident), ]`: Export a mocking API as a module with the given name
 /// * `#[unimock(api=[method1, method2, ..], )]`: Instead of generating a module, generate top-level mock structs for the methods in the trait,
-///     with the names of those structs passed with array-like syntax in the same order as the methods appear in the trait definition.
+///   with the names of those structs passed with array-like syntax in the same order as the methods appear in the trait definition.
 /// * `#[unimock(unmock_with=[a, b, _], )]`: Given there are e.g. 3 methods in the annotated trait, uses the given paths as unmock implementations.
-///     The functions are assigned to the methods in the same order as the methods are listed in the trait.
-///     A value of `_` means _no unmock support_ for that method.
+///   The functions are assigned to the methods in the same order as the methods are listed in the trait.
+///   A value of `_` means _no unmock support_ for that method.
 /// * `#[unimock(prefix=path, )]`: Makes unimock use a different path prefix than `::unimock`, in case the crate has been re-exported through another crate.
 /// * `#[unimock(type #ident = #assoc; )]`: Specify the value of the associated type `#ident`.
+/// * `#[unimock(standalone=#ident, )]`: Also generate a newtype `#ident(Unimock)` which implements only the annotated trait, by delegating to [Unimock].
+///   Use it to prove that a component does not need more traits than it declares, or where [Unimock] already implements a trait with conflicting method names.
+/// * `#[unimock(debug_inputs=path, )]`: Format inputs that do not implement [Debug](core::fmt::Debug) with the function `path`, which has the signature `fn(&T) -> String`,
+///   instead of leaving them out of diagnostics. [input::type_summary] is a formatter that works for any type.
+///   A single parameter can be given its own formatter with `#[unimock(debug_inputs = path)] param: T`.
+/// * `#[unimock(with_supertraits, )]`: Also mock the supertraits of the trait. Requires `api=#ident`. Every supertrait must be exported with [`#[export]`](macro@export),
+///   and gets a mock API module nested in the trait's mock module, e.g. `RepoMock::Reader::read`. Supertraits are mocked transitively,
+///   and marker traits that [Unimock] already implements, like `Send` and `Clone`, are skipped.
+///   A supertrait shared by several traits in the tree is mocked once, and its module is reachable from each of them, e.g. as both `RepoMock::Base` and `RepoMock::Reader::Base`.
+///   A bare supertrait name like `Debug` is taken to be the standard library trait, so a trait of your own with the same name must be written as a path, e.g. `self::Debug`.
+///   A supertrait that is not exported fails to compile with an error about its missing `__unimock_export_*` macro.
+/// * `#[unimock(api_style="PascalCase", )]`: Naming style of the mock structs in the `api` module. Accepts `"snake_case"` (the default), `"PascalCase"`,
+///   or a template where `{method}` is replaced by the method name and `{Method}` by the method name in PascalCase, e.g. `"Mock{Method}"`.
+///
+/// A mock `api` module also contains `ALL`, a list of the [MockFnInfo] of every method of the trait, which is used with [Unimock::coverage_report].
+/// Generic methods are listed once, by their generic mock entry.
+#[allow(clippy::useless_attribute, clippy::test_attr_in_doctest)]
 pub use unimock_macros::unimock;
 
+///
+/// Export a trait definition, so that crates depending on the trait's crate can mock it using [mock_trait!].
+///
+/// The attribute defines a hidden `#[macro_export]` macro at the root of the crate, named after the trait.
+/// Therefore traits exported from the same crate need distinct names.
+///
+/// Paths in the trait's method signatures are resolved in the crate that mocks the trait,
+/// except for `crate::`-relative paths, which keep pointing into the exporting crate.
+///
+/// # Example
+/// ```rust
+/// #[unimock::export]
+/// pub trait Store {
+///     fn load(&self, key: &str) -> Option<String>;
+/// }
+/// ```
+pub use unimock_macros::export;
+
+///
+/// Mock a trait that has been exported with [`#[export]`](macro@export), by its path.
+///
+/// The first argument is the path to the trait. Any remaining arguments are passed on to the [unimock](macro@unimock) attribute.
+/// A trait exported from the current crate is named by its bare name, or by a path starting with `crate::`, `self::` or `super::`.
+/// Any other path starts with the name of the crate that exports the trait.
+/// The trait itself is not redeclared, the path works like the `mirror` argument.
+///
+/// The generated `impl Trait for Unimock` is subject to Rust's orphan rules.
+/// It compiles in the crate that defines the trait (e.g. in a test-only module), or in unimock itself,
+/// but not in a third crate.
+/// To mock the trait from any other crate, pass `standalone=#ident`: then only the newtype `#ident(Unimock)`, which is owned by
+/// the mocking crate, implements the trait. Default method implementations cannot be delegated to in that mode, so they must be mocked.
+///
+/// # Example
+/// ```rust
+/// # mod other_crate {
+/// #[unimock::export]
+/// pub trait Store {
+///     fn load(&self, key: &str) -> Option<String>;
+/// }
+/// # }
+/// # use other_crate::*;
+/// use unimock::*;
+///
+/// // a path into another crate is written `mock_trait!(other_crate::Store, api=StoreMock)`
+/// mock_trait!(Store, api=StoreMock);
+///
+/// let u = Unimock::new(
+///     StoreMock::load
+///         .next_call(matching!("key"))
+///         .returns(Some("value".to_string())),
+/// );
+///
+/// assert_eq!(Some("value".to_string()), u.load("key"));
+///
+/// // in a third crate
+/// mock_trait!(Store, api=ThirdStoreMock, standalone=MockStore);
+///
+/// let store = MockStore(Unimock::new(
+///     ThirdStoreMock::load
+///         .next_call(matching!("key"))
+///         .returns(None::<String>),
+/// ));
+///
+/// assert_eq!(None, store.load("key"));
+/// ```
+pub use unimock_macros::mock_trait;
+
+#[doc(hidden)]
+pub use unimock_macros::__mock_exported_trait;
+
+#[doc(hidden)]
+pub use unimock_macros::__mock_supertrait;
+
 ///
 /// Macro to ease _call pattern_ matching for function arguments.
 /// The macro produces a closure reference expression suitable for passing to [`some_call`](MockFn::some_call), etc.
@@ -723,6 +912,14 @@ pub use unimock_macros::unimock;
 ///
 pub use unimock_macros::matching;
 
+#[cfg(feature = "std")]
+pub use current::EnterGuard;
+pub use fixture::Fixture;
+pub use policy::Policy;
+#[cfg(feature = "std")]
+pub use verifier::Verifier;
+pub use wait::UntilCalled;
+
 #[derive(Clone, Copy)]
 enum FallbackMode {
     Error,
@@ -734,7 +931,7 @@ enum FallbackMode {
 /// All traits implemented by Unimock can be considered mock implementations, except _marker traits_, [Clone] and [Drop].
 ///
 /// The mock configuration is specified up front, as a constructor argument in the form of a simple or compound [Clause].
-/// After instantiation, the unimock configuration is immutable.
+/// After instantiation, the unimock configuration can only grow, by adding clauses with [Unimock::extend].
 ///
 /// Unimock implements [Send](Send) and [Sync](Sync), and is therefore thread safe.
 ///
@@ -764,6 +961,9 @@ pub struct Unimock {
 
     default_impl_delegator_cell: OnceCell<alloc::Box<DefaultImplDelegator>>,
 
+    // Calls only match call patterns scoped to the same tag.
+    tag: Option<&'static str>,
+
     original_instance: bool,
     torn_down: bool,
     verify_in_drop: bool,
@@ -853,6 +1053,139 @@ impl Unimock {
         self
     }
 
+    /// Set up more call patterns for this instance and all of its clones, after it was created.
+    ///
+    /// This suits tests that run in phases, which set up the expectations of each phase when it starts.
+    /// Calls are matched against the newest call patterns of a [MockFn] first.
+    /// Ordered call patterns (see [MockFn::next_call]) are placed after the ones already set up in the call order.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use unimock::*;
+    /// #[unimock(api=DoorMock)]
+    /// trait Door {
+    ///     fn is_open(&self) -> bool;
+    /// }
+    ///
+    /// let u = Unimock::new(DoorMock::is_open.next_call(matching!()).returns(false));
+    /// assert!(!u.is_open());
+    ///
+    /// u.extend(DoorMock::is_open.next_call(matching!()).returns(true));
+    /// assert!(u.is_open());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the clause sets up a [MockFn] with [MockFn::next_call] that was set up with [MockFn::each_call] or [MockFn::some_call] before, or the other way around.
+    #[track_caller]
+    pub fn extend(&self, clause: impl Clause) {
+        if let Err(error) = self.shared_state.extend(clause) {
+            panic!("{error}");
+        }
+    }
+
+    /// Verify the call counts and the call order of the call patterns set up so far.
+    ///
+    /// This finds failed expectations in long tests close to where they happen, instead of when the instance is dropped.
+    /// A checkpoint starts a fresh phase of expectations, set up with [Unimock::extend]:
+    /// The call order continues after the ordered call patterns set up so far,
+    /// and call patterns with an exact call count, like those set up with [MockFn::next_call] or `.once()`, no longer match calls.
+    /// Other call patterns, like stubs set up with [MockFn::each_call], keep answering calls, but their call counts are not verified again.
+    /// Mock fns that have not been called yet are not reported as dead mocks until the final verification.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use unimock::*;
+    /// #[unimock(api=PumpMock)]
+    /// trait Pump {
+    ///     fn start(&self);
+    ///     fn stop(&self);
+    /// }
+    ///
+    /// let u = Unimock::new(PumpMock::start.next_call(matching!()).returns(()));
+    /// u.start();
+    /// u.checkpoint();
+    ///
+    /// u.extend(PumpMock::stop.next_call(matching!()).returns(()));
+    /// u.stop();
+    /// ```
+    ///
+    /// # Panics
+    /// Panics with the location of the checkpoint if verification fails.
+    #[track_caller]
+    pub fn checkpoint(&self) {
+        if let Err(errors) = self.shared_state.checkpoint() {
+            #[cfg(not(feature = "std"))]
+            self.panicked.locked(|panicked| {
+                *panicked = true;
+            });
+
+            let location = core::panic::Location::caller();
+            teardown::panic_with_errors(
+                Some(format_args!("Unimock checkpoint at {location} failed:")),
+                errors,
+            );
+        }
+    }
+
+    /// Verify calls according to the given [Policy], instead of [Policy::strict].
+    ///
+    /// # Panics
+    /// Panics if the instance has already been cloned.
+    #[track_caller]
+    pub fn with_policy(mut self, policy: Policy) -> Self {
+        self.configure_before_clone("with_policy").policy = policy;
+        self
+    }
+
+    /// Make ordered calls wait for their turn in the call order, for at most `timeout`.
+    ///
+    /// By default, an ordered call (see [MockFn::next_call]) takes the next position in the call order as soon as it arrives,
+    /// so calls made concurrently from several threads get matched in whatever order they happen to race.
+    /// With a blocking call order, an ordered call instead blocks until the next call pattern in line matches it,
+    /// which effectively turns the mock setup into a scheduler for the calling threads.
+    ///
+    /// A call that has not had its turn within `timeout` fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use unimock::*;
+    /// # use std::time::Duration;
+    /// #[unimock(api=LogMock)]
+    /// trait Log {
+    ///     fn log(&self, message: &str);
+    /// }
+    ///
+    /// let u = Unimock::new((
+    ///     LogMock::log.next_call(matching!("first")).returns(()),
+    ///     LogMock::log.next_call(matching!("second")).returns(()),
+    /// ))
+    /// .blocking_call_order(Duration::from_secs(5));
+    ///
+    /// let u2 = u.clone();
+    /// let thread = std::thread::spawn(move || u2.log("second"));
+    /// u.log("first");
+    /// thread.join().unwrap();
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the instance has already been cloned.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub fn blocking_call_order(mut self, timeout: std::time::Duration) -> Self {
+        self.configure_before_clone("blocking_call_order")
+            .call_order_timeout = Some(timeout);
+        self
+    }
+
+    /// Access the shared state for configuration, which is only possible before the instance gets cloned.
+    #[track_caller]
+    fn configure_before_clone(&mut self, method: &str) -> &mut state::SharedState {
+        match alloc::Arc::get_mut(&mut self.shared_state) {
+            Some(shared_state) => shared_state,
+            None => panic!("Called {method}() after the instance was cloned. Configure the instance before cloning it."),
+        }
+    }
+
     /// Explicitly verify this unimock instance.
     ///
     /// There is no need to do this explicitly unless [Self::no_verify_in_drop] has been called.
@@ -864,6 +1197,180 @@ impl Unimock {
         teardown::teardown_panic(&mut self);
     }
 
+    /// Hand over verification to a [Verifier], which can stay on the test thread while this instance moves to another thread.
+    ///
+    /// After this call, neither this instance nor its clones verify calls when dropped, and this instance may be dropped on any thread.
+    /// [Verifier::wait_and_verify] waits until they have all been dropped, then verifies.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use unimock::*;
+    /// # use std::time::Duration;
+    /// #[unimock(api=ActorMock)]
+    /// trait Actor {
+    ///     fn handle(&self, message: i32);
+    /// }
+    ///
+    /// let mut u = Unimock::new(ActorMock::handle.next_call(matching!(42)).returns(()));
+    /// let verifier = u.verifier();
+    ///
+    /// std::thread::spawn(move || u.handle(42));
+    ///
+    /// verifier.wait_and_verify(Duration::from_secs(5));
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use = "calls are not verified unless the verifier is used"]
+    pub fn verifier(&mut self) -> Verifier {
+        if !self.original_instance {
+            panic!("Called verifier() on a cloned instance. Call it on the original instance instead.");
+        }
+
+        self.original_instance = false;
+        Verifier::new(self.shared_state.clone())
+    }
+
+    /// Block the current thread until `F` has been called `times` times in total, by this instance or its clones.
+    ///
+    /// Only calls matched by a call pattern of `F` are counted. The answer of the last counted call may still be in progress.
+    /// Panics if the calls have not been made within `timeout`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use unimock::*;
+    /// # use std::time::Duration;
+    /// #[unimock(api=WorkerMock)]
+    /// trait Worker {
+    ///     fn work(&self, job: i32);
+    /// }
+    ///
+    /// let u = Unimock::new(WorkerMock::work.each_call(matching!(_)).returns(()));
+    ///
+    /// let clone = u.clone();
+    /// let handle = std::thread::spawn(move || {
+    ///     clone.work(1);
+    ///     clone.work(2);
+    /// });
+    ///
+    /// u.wait_for::<WorkerMock::work>(2, Duration::from_secs(5));
+    /// # handle.join().unwrap();
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub fn wait_for<F: MockFn>(&self, times: usize, timeout: std::time::Duration) {
+        wait::wait_for::<F>(&self.shared_state, times, timeout)
+    }
+
+    /// Wait asynchronously until `F` has been called `times` times in total, by this instance or its clones.
+    ///
+    /// Only calls matched by a call pattern of `F` are counted.
+    /// The future has no timeout of its own, so combine it with the timer of the async runtime.
+    pub fn until_called<F: MockFn>(&self, times: usize) -> UntilCalled<'_, F> {
+        UntilCalled::new(self, times)
+    }
+
+    /// Create a clone of this instance that acts as a separate implementation of the mocked traits.
+    ///
+    /// Calls on the tagged clone only match call patterns scoped to the same tag with [MockFn::tag],
+    /// and calls on untagged instances only match call patterns without a tag.
+    /// This makes it possible to tell apart several implementations of one trait that are passed to the same component.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use unimock::*;
+    /// #[unimock(api=StorageMock)]
+    /// trait Storage {
+    ///     fn get(&self, key: &str) -> Option<i32>;
+    /// }
+    ///
+    /// fn read(primary: &impl Storage, replica: &impl Storage, key: &str) -> Option<i32> {
+    ///     primary.get(key).or_else(|| replica.get(key))
+    /// }
+    ///
+    /// let primary = Unimock::new((
+    ///     StorageMock::get
+    ///         .some_call(matching!("a"))
+    ///         .returns(None),
+    ///     StorageMock::get
+    ///         .tag("replica")
+    ///         .some_call(matching!("a"))
+    ///         .returns(Some(42)),
+    /// ));
+    /// let replica = primary.tagged("replica");
+    ///
+    /// assert_eq!(Some(42), read(&primary, &replica, "a"));
+    /// ```
+    pub fn tagged(&self, tag: &'static str) -> Self {
+        let mut clone = self.clone();
+        clone.tag = Some(tag);
+        clone
+    }
+
+    /// Make this instance the current one on the calling thread, until the returned guard is dropped.
+    ///
+    /// Associated functions without a `self` receiver, like constructors, have no [Unimock] instance to evaluate their calls with.
+    /// Their mock implementations use the current instance of the calling thread instead.
+    /// Since every test runs in its own thread, tests running in parallel do not interfere.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use unimock::*;
+    /// #[unimock(api=ServerMock)]
+    /// trait Server {
+    ///     fn default_port() -> u16;
+    /// }
+    ///
+    /// fn port<S: Server>() -> u16 {
+    ///     S::default_port()
+    /// }
+    ///
+    /// let u = Unimock::new(ServerMock::default_port.some_call(matching!()).returns(8080_u16));
+    /// let _guard = u.enter();
+    ///
+    /// assert_eq!(8080, port::<Unimock>());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn enter(&self) -> EnterGuard {
+        current::enter(self)
+    }
+
+    /// Report which of the given methods were never stubbed, or stubbed but never called, by this instance and its clones.
+    ///
+    /// The methods of a trait are listed by the `ALL` constant of its mock module.
+    /// Calls are counted when they are answered by a clause or a [TypeFactory](factory::TypeFactory).
+    ///
+    /// # Example
+    /// ```rust
+    /// use unimock::*;
+    ///
+    /// #[unimock(api=StoreMock)]
+    /// trait Store {
+    ///     fn load(&self) -> i32;
+    ///     fn save(&self, value: i32);
+    ///     fn clear(&self);
+    /// }
+    ///
+    /// let u = Unimock::new((
+    ///     StoreMock::load.some_call(matching!()).returns(1),
+    ///     StoreMock::save.some_call(matching!(_)).returns(()),
+    /// ))
+    /// // `save` is never called
+    /// .no_verify_in_drop();
+    ///
+    /// u.load();
+    ///
+    /// let report = u.coverage_report(&[StoreMock::ALL]);
+    /// assert_eq!(
+    ///     "Store::clear: never stubbed\nStore::save: stubbed, but never called\n",
+    ///     report.to_string(),
+    /// );
+    /// ```
+    pub fn coverage_report(
+        &self,
+        mock_fns: &[&[fn() -> MockFnInfo]],
+    ) -> coverage::CoverageReport {
+        coverage::CoverageReport::new(&self.shared_state, mock_fns)
+    }
+
     /// Convert the given value into a reference.
     ///
     /// This can be useful when returning references from `answers` functions.
@@ -904,15 +1411,16 @@ impl Unimock {
         assembler_result: Result<MockAssembler, alloc::String>,
         fallback_mode: FallbackMode,
     ) -> Self {
-        let fn_mockers = match assembler_result {
+        let mockers = match assembler_result {
             Ok(assembler) => assembler.finish(),
             Err(error) => panic!("{error}"),
         };
 
         Self {
-            shared_state: alloc::Arc::new(state::SharedState::new(fn_mockers, fallback_mode)),
+            shared_state: alloc::Arc::new(state::SharedState::new(mockers, fallback_mode)),
             value_chain: Default::default(),
             default_impl_delegator_cell: Default::default(),
+            tag: None,
             original_instance: true,
             torn_down: false,
             verify_in_drop: true,
@@ -937,6 +1445,8 @@ impl Unimock {
             });
         }
 
+        error.log();
+
         let msg = alloc::format!("{error}");
 
         self.shared_state.panic_reasons.locked(move |reasons| {
@@ -949,10 +1459,16 @@ impl Unimock {
 
 impl Clone for Unimock {
     fn clone(&self) -> Unimock {
+        #[cfg(feature = "std")]
+        self.shared_state
+            .live_instances
+            .fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+
         Unimock {
             shared_state: self.shared_state.clone(),
             value_chain: Default::default(),
             default_impl_delegator_cell: Default::default(),
+            tag: self.tag,
             original_instance: false,
             torn_down: false,
             verify_in_drop: self.verify_in_drop,
@@ -984,11 +1500,25 @@ impl RefUnwindSafe for Unimock {}
 
 impl Drop for Unimock {
     fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        {
+            self.shared_state
+                .live_instances
+                .fetch_sub(1, core::sync::atomic::Ordering::SeqCst);
+            self.shared_state.call_notifier.notify();
+        }
+
         if self.torn_down {
             return;
         }
 
         if self.verify_in_drop {
+            #[cfg(feature = "std")]
+            if self.shared_state.policy.report_on_drop {
+                teardown::teardown_report(self);
+                return;
+            }
+
             teardown::teardown_panic(self);
         }
     }
@@ -1012,6 +1542,7 @@ impl Drop for Unimock {
 /// The `mock-std` feature also enables mocking of this trait through [mock::std::process::TerminationMock].
 /// This trait mock is partial by default: Unless explicitly mocked, it behaves as specified above.
 #[cfg(feature = "std")]
+#[allow(clippy::test_attr_in_doctest)]
 impl std::process::Termination for Unimock {
     #[cfg(feature = "mock-std")]
     fn report(mut self) -> std::process::ExitCode {
@@ -1097,11 +1628,18 @@ pub trait MockFn: Sized + 'static {
     where
         E: FnOnce(&mut build::Each<Self>),
     {
-        let mut each = build::Each::new();
+        let mut each = build::Each::new(None);
         each_fn(&mut each);
         each
     }
 
+    /// Scope call patterns to the [Unimock] instances created with [Unimock::tagged] using the same tag.
+    ///
+    /// Call patterns defined without a tag only match calls on untagged instances.
+    fn tag(self, tag: &'static str) -> build::Tagged<Self> {
+        build::Tagged::new(tag)
+    }
+
     /// Define a stub-like call pattern directly on this [MockFn].
     ///
     /// This is a shorthand to avoid calling [MockFn::stub] if there is only one call pattern
@@ -1196,6 +1734,14 @@ pub struct MockFnInfo {
     path: TraitMethodPath,
     has_default_impl: bool,
     partial_by_default: bool,
+    generic: Option<GenericInfo>,
+}
+
+/// The generic mock entry that a [MockFnInfo] is an instantiation of.
+#[derive(Clone, Copy)]
+struct GenericInfo {
+    untyped_type_id: TypeId,
+    type_args: fn() -> alloc::Vec<alloc::String>,
 }
 
 impl MockFnInfo {
@@ -1210,6 +1756,21 @@ impl MockFnInfo {
             path: TraitMethodPath::default(),
             has_default_impl: false,
             partial_by_default: false,
+            generic: None,
+        }
+    }
+
+    fn generic_entry<U: 'static>() -> Self {
+        Self::with_type_id(TypeId::of::<U>()).generic::<U>(alloc::Vec::new)
+    }
+
+    /// Whether the method is an instantiation of the generic method described by `entry`, or the same method.
+    pub(crate) fn is_covered_by(&self, entry: &MockFnInfo) -> bool {
+        match (self.generic, entry.generic) {
+            (Some(generic), Some(entry_generic)) => {
+                generic.untyped_type_id == entry_generic.untyped_type_id
+            }
+            _ => self.type_id == entry.type_id,
         }
     }
 
@@ -1228,6 +1789,28 @@ impl MockFnInfo {
             ..self
         }
     }
+
+    /// The name of the trait the method belongs to.
+    pub fn trait_ident(&self) -> &'static str {
+        self.path.trait_ident()
+    }
+
+    /// The name of the method.
+    pub fn method_ident(&self) -> &'static str {
+        self.path.method_ident()
+    }
+
+    /// Mark the method as one instantiation of the generic mock entry `U`, with the names of its type arguments and the values of its const arguments.
+    #[doc(hidden)]
+    pub fn generic<U: 'static>(self, type_args: fn() -> alloc::Vec<alloc::String>) -> Self {
+        Self {
+            generic: Some(GenericInfo {
+                untyped_type_id: TypeId::of::<U>(),
+                type_args,
+            }),
+            ..self
+        }
+    }
 }
 
 /// A marker type used when Unimock is unable to represent the user's type.
@@ -91,6 +91,14 @@ impl Display for Mismatches {
                         Diff::new(actual, expected).fmt(f)?;
                     }
                 }
+                (MismatchKind::Eq, Some(actual), None) => {
+                    header_msg.fmt(f)?;
+                    writeln!(f, "Actual value did not equal expected value, but can't display the expected value because the type is likely missing #[derive(Debug)]. Actual value: {actual}")?;
+                }
+                (MismatchKind::Ne, Some(actual), None) => {
+                    header_msg.fmt(f)?;
+                    writeln!(f, "Actual value unexpectedly equalled expected value, but can't display the expected value because the type is likely missing #[derive(Debug)]. Actual value: {actual}")?;
+                }
                 (MismatchKind::Pattern, _, _) => {
                     header_msg.fmt(f)?;
                     writeln!(f, "Actual value did not match expected pattern, but can't display diagnostics because the type is likely missing #[derive(Debug)].")?;
@@ -1,7 +1,9 @@
 //! Traits for modelling the output of MockFns used with `returns`.
 
-use crate::alloc::Box;
+use crate::alloc::{Arc, Box, Vec};
+use crate::MockFn;
 
+pub(crate) mod boxing;
 pub(crate) mod deep;
 pub(crate) mod lending;
 pub(crate) mod mut_lending;
@@ -22,6 +24,14 @@ type OutputResult<T> = Result<T, OutputError>;
 pub trait Kind: 'static {
     /// A type used to store return values
     type Return: GetOutput;
+
+    /// Produce an output directly from a [TypeFactory](crate::factory::TypeFactory).
+    #[doc(hidden)]
+    fn produce<'u>(
+        _factory: &'u crate::factory::TypeFactory,
+    ) -> Result<<Self::Return as GetOutput>::Output<'u>, crate::factory::ProduceError> {
+        Err(crate::factory::ProduceError::UnsupportedOutput)
+    }
 }
 
 /// A [Kind] that may be used with `returns` combinators.
@@ -60,9 +70,44 @@ pub trait ReturnDefault<K: Kind> {
     fn return_default() -> K::Return;
 }
 
+/// A [MockFn] that lends out references to its `self` receiver, and can compute those values with owned answers.
+///
+/// This trait is implemented by the [unimock](crate::unimock) macro for methods returning `&T`, `Option<&T>` or `Result<&T, E>`,
+/// and enables [`answers_owned`](crate::build::DefineResponse::answers_owned).
+pub trait LendingAnswer: MockFn {
+    /// The type behind the returned reference.
+    type Lent: ?Sized + 'static;
+
+    /// The function type used for function application, when the function produces an owned value of type `O` in place of `&Lent`.
+    type OwnedAnswerFn<O: 'static>: ?Sized + Send + Sync;
+
+    /// Convert an owned answer function into an [AnswerFn](MockFn::AnswerFn),
+    /// which stores its answers in the [Unimock](crate::Unimock) instance.
+    #[doc(hidden)]
+    fn lend_answer<O>(answer_fn: &'static Self::OwnedAnswerFn<O>) -> Arc<Self::AnswerFn>
+    where
+        O: core::borrow::Borrow<Self::Lent> + Send + Sync + 'static;
+}
+
+/// A [MockFn] returning a boxed iterator or stream, which can respond with a sequence of items.
+///
+/// This trait is implemented by the [unimock](crate::unimock) macro for methods returning `Box<dyn Iterator<Item = T>>` or `Pin<Box<dyn Stream<Item = T>>>`,
+/// where `T` is either owned or borrowed from `self`, and enables [`returns_items`](crate::build::DefineResponse::returns_items).
+pub trait ReturnsItems: MockFn {
+    /// The stored item type. For items borrowed from `self`, this is the type behind the reference.
+    type Item: Clone + Send + Sync + 'static;
+
+    /// Make a return value that outputs a fresh iterator or stream over the items on each call.
+    #[doc(hidden)]
+    fn items_return(
+        items: Vec<Self::Item>,
+    ) -> Result<<Self::OutputKind as Kind>::Return, OutputError>;
+}
+
 /// A "marker" for mutable types
 pub struct Mutable<T>(pub(crate) T);
 
+pub use boxing::{BoxedOutput, Boxing, IntoBoxedOutput};
 pub use deep::Deep;
 pub use lending::Lending;
 pub use mut_lending::MutLending;
@@ -0,0 +1,71 @@
+use crate::output::owning::Owned;
+use crate::output::*;
+
+/// A type category for return-position `impl Trait` values, which are stored as boxed trait objects.
+///
+/// The parameter `F` is the [MockFn](crate::MockFn) which knows how to box its output.
+#[doc(hidden)]
+pub struct Boxing<F>(core::marker::PhantomData<fn() -> F>);
+
+impl<F: BoxedOutput> Kind for Boxing<F> {
+    type Return = Owned<Box<F::Dyn>>;
+}
+
+impl<F: BoxedOutput> Return for Boxing<F> {
+    type Type = Box<F::Dyn>;
+}
+
+impl<T0, F> IntoReturnOnce<Boxing<F>> for T0
+where
+    T0: Send + Sync + 'static,
+    F: IntoBoxedOutput<T0>,
+{
+    #[cfg(any(feature = "std", feature = "spin-lock"))]
+    fn into_return_once(self) -> OutputResult<Owned<Box<F::Dyn>>> {
+        // The value is boxed on output, so the trait object itself need not be `Send` or `Sync`
+        let mutex = crate::private::MutexIsh::new(Some(self));
+        Ok(Owned(Box::new(move || {
+            mutex.locked(|option| option.take()).map(F::into_boxed)
+        })))
+    }
+
+    #[cfg(not(any(feature = "std", feature = "spin-lock")))]
+    fn into_return_once(self) -> OutputResult<Owned<Box<F::Dyn>>> {
+        Err(OutputError::NoMutexApi)
+    }
+}
+
+impl<T0, F> IntoReturn<Boxing<F>> for T0
+where
+    T0: Clone + Send + Sync + 'static,
+    F: IntoBoxedOutput<T0>,
+{
+    fn into_return(self) -> OutputResult<Owned<Box<F::Dyn>>> {
+        Ok(Owned(Box::new(move || Some(F::into_boxed(self.clone())))))
+    }
+}
+
+/// A mocked function returning `impl Trait`, where the output is represented as a trait object.
+///
+/// This trait is implemented by the [unimock](crate::unimock) macro.
+/// The trait object keeps the auto traits of the `impl Trait`, and can have only one other trait:
+///
+/// ```compile_fail
+/// # use unimock::*;
+/// #[unimock(api=TraitMock)]
+/// trait Trait {
+///     fn get(&self) -> impl core::fmt::Display + core::fmt::Debug;
+/// }
+/// ```
+pub trait BoxedOutput: 'static {
+    /// The trait object type, e.g. `dyn Iterator<Item = T>`.
+    type Dyn: ?Sized + 'static;
+}
+
+/// Conversion of a value of type `T` into the boxed output of a [BoxedOutput].
+///
+/// This trait is implemented by the [unimock](crate::unimock) macro for every `T` implementing the returned trait.
+pub trait IntoBoxedOutput<T>: BoxedOutput {
+    /// Box the value.
+    fn into_boxed(value: T) -> Box<Self::Dyn>;
+}
@@ -0,0 +1,43 @@
+use crate::alloc::vec::Vec;
+use crate::output::*;
+
+/// Boxed iterators (and streams) are returned by collecting the items into a [Vec],
+/// which the mocked method then turns into a fresh iterator on each call.
+type Mix<K> = Deep<Box<dyn Iterator<Item = K>>>;
+
+impl<K> Kind for Mix<K>
+where
+    K: Kind,
+{
+    type Return = <Deep<Vec<K>> as Kind>::Return;
+}
+
+impl<K> Return for Mix<K>
+where
+    K: Return,
+    <K as Return>::Type: 'static + Send + Sync,
+{
+    type Type = Vec<<K as Return>::Type>;
+}
+
+impl<T, K> IntoReturnOnce<Mix<K>> for Vec<T>
+where
+    K: Return,
+    <K as Return>::Type: 'static + Send + Sync,
+    T: IntoReturnOnce<K>,
+{
+    fn into_return_once(self) -> OutputResult<<Mix<K> as Kind>::Return> {
+        <Self as IntoReturnOnce<Deep<Vec<K>>>>::into_return_once(self)
+    }
+}
+
+impl<T, K> IntoReturn<Mix<K>> for Vec<T>
+where
+    K: Return,
+    <K as Return>::Type: 'static + Send + Sync,
+    T: IntoReturn<K>,
+{
+    fn into_return(self) -> OutputResult<<Mix<K> as Kind>::Return> {
+        <Self as IntoReturn<Deep<Vec<K>>>>::into_return(self)
+    }
+}
@@ -1,3 +1,4 @@
+mod iter;
 mod option;
 mod poll;
 mod result;
@@ -6,6 +6,10 @@ pub struct Owning<T>(core::marker::PhantomData<fn() -> T>);
 
 impl<T: 'static> Kind for Owning<T> {
     type Return = Owned<T>;
+
+    fn produce(factory: &crate::factory::TypeFactory) -> Result<T, crate::factory::ProduceError> {
+        factory.produce::<T>()
+    }
 }
 
 impl<T: 'static> Return for Owning<T> {
@@ -47,7 +51,7 @@ impl<T: Default + 'static> ReturnDefault<Owning<T>> for T {
     }
 }
 
-pub struct Owned<T>(Box<dyn Fn() -> Option<T> + Send + Sync + 'static>);
+pub struct Owned<T>(pub(crate) Box<dyn Fn() -> Option<T> + Send + Sync + 'static>);
 
 impl<T: 'static> GetOutput for Owned<T> {
     type Output<'u> = T where Self: 'u;
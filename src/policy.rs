@@ -0,0 +1,91 @@
+use crate::error::MockError;
+
+/// Policy for how a [Unimock](crate::Unimock) verifies the calls made to it, set with [Unimock::with_policy](crate::Unimock::with_policy).
+///
+/// The default policy is [Policy::strict]: Mock fns that are never called are reported as dead mocks,
+/// every call count expectation must be met exactly, and failed verifications panic when the instance is dropped.
+///
+/// # Example
+/// ```rust
+/// # use unimock::*;
+/// #[unimock(api=StoreMock)]
+/// trait Store {
+///     fn get(&self, key: &str) -> Option<i32>;
+///     fn put(&self, key: &str, value: i32);
+/// }
+///
+/// // A setup shared by several tests, some of which never call `put`:
+/// fn store_fixture() -> impl Clause {
+///     (
+///         StoreMock::get.each_call(matching!(_)).returns(None),
+///         StoreMock::put.each_call(matching!(_, _)).returns(()),
+///     )
+/// }
+///
+/// let u = Unimock::new(store_fixture()).with_policy(Policy::strict().allow_unused());
+/// assert_eq!(None, u.get("key"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Policy {
+    pub(crate) allow_unused: bool,
+    pub(crate) extra_calls_as_warnings: bool,
+    #[cfg(feature = "std")]
+    pub(crate) report_on_drop: bool,
+}
+
+impl Policy {
+    /// The strictest policy, which is the default.
+    pub const fn strict() -> Self {
+        Self {
+            allow_unused: false,
+            extra_calls_as_warnings: false,
+            #[cfg(feature = "std")]
+            report_on_drop: false,
+        }
+    }
+
+    /// Don't report mock fns that were never called as dead mocks.
+    ///
+    /// This suits setups shared between tests, where each test only calls some of the mocked methods.
+    /// Call patterns with an explicit call count, like `.once()`, are still verified.
+    pub const fn allow_unused(mut self) -> Self {
+        self.allow_unused = true;
+        self
+    }
+
+    /// Report call patterns that matched more calls than their exact call count as warnings, instead of failing.
+    ///
+    /// Warnings are printed to stderr with the `std` feature, and logged with the `defmt-1` feature.
+    /// Without either, they are discarded.
+    pub const fn extra_calls_as_warnings(mut self) -> Self {
+        self.extra_calls_as_warnings = true;
+        self
+    }
+
+    /// Print failed verifications to stderr when the instance is dropped, instead of panicking.
+    ///
+    /// Explicit verification with [Unimock::verify](crate::Unimock::verify) still panics.
+    #[cfg(feature = "std")]
+    pub const fn report_on_drop(mut self) -> Self {
+        self.report_on_drop = true;
+        self
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "std", feature = "defmt-1")),
+    allow(unused_variables)
+)]
+pub(crate) fn warn(error: &MockError) {
+    #[cfg(feature = "std")]
+    std::eprintln!("unimock warning: {error}");
+
+    #[cfg(feature = "defmt-1")]
+    defmt::warn!("unimock warning: {}", error);
+}
@@ -142,6 +142,15 @@ impl MismatchReporter {
         }
     }
 
+    /// Use the debug representation of the inputs for mismatched values that could not be displayed by the matcher.
+    pub(crate) fn fill_actual(&mut self, inputs_debug: &[Option<String>]) {
+        for (InputIndex(index), mismatch) in &mut self.mismatches {
+            if mismatch.actual.is_none() {
+                mismatch.actual = inputs_debug.get(*index).cloned().flatten();
+            }
+        }
+    }
+
     /// Whether debugging is enabled
     pub fn enabled(&self) -> bool {
         self.enabled
@@ -208,6 +217,17 @@ where
     unimock.handle_error(eval::eval(unimock, inputs))
 }
 
+/// The current Unimock instance of the thread, for evaluating a [MockFn] without a `self` receiver.
+#[track_caller]
+pub fn current_unimock<F: MockFn>() -> Unimock {
+    crate::current::current::<F>()
+}
+
+/// Information about the generic mock entry `U`, standing for all instantiations of a generic method.
+pub fn generic_entry_info<U: 'static>() -> MockFnInfo {
+    MockFnInfo::generic_entry::<U>()
+}
+
 /// Clone a Unimock instance
 pub fn clone_unimock(unimock: &Unimock) -> Unimock {
     unimock.clone()
@@ -217,12 +237,18 @@ pub fn clone_unimock(unimock: &Unimock) -> Unimock {
 pub trait ProperDebug {
     /// Optionally format a debug representation.
     fn unimock_try_debug(&self) -> Option<String>;
+
+    /// Format a debug representation, ignoring the fallback formatter.
+    fn unimock_try_debug_or(&self, fallback: fn(&Self) -> String) -> Option<String>;
 }
 
 /// Fallback trait (using autoref specialization) for returning [None] when the implementing value does not implement [std::fmt::Debug].
-pub trait NoDebug {
+pub trait NoDebug<T: ?Sized> {
     /// Optionally format a debug representation.
     fn unimock_try_debug(&self) -> Option<String>;
+
+    /// Format the value using the fallback formatter.
+    fn unimock_try_debug_or(&self, fallback: fn(&T) -> String) -> Option<String>;
 }
 
 // Autoref specialization:
@@ -232,12 +258,20 @@ impl<T: core::fmt::Debug> ProperDebug for T {
     fn unimock_try_debug(&self) -> Option<String> {
         Some(crate::alloc::format!("{self:?}"))
     }
+
+    fn unimock_try_debug_or(&self, _fallback: fn(&Self) -> String) -> Option<String> {
+        self.unimock_try_debug()
+    }
 }
 
-impl<T> NoDebug for &T {
+impl<T> NoDebug<T> for &T {
     fn unimock_try_debug(&self) -> Option<String> {
         None
     }
+
+    fn unimock_try_debug_or(&self, fallback: fn(&T) -> String) -> Option<String> {
+        Some(fallback(self))
+    }
 }
 
 /// Convert any type implementing `AsRef<str>` to a `&str`.
@@ -327,3 +361,61 @@ impl<T> MutexIsh<T> {
         func(&mut self.inner.borrow_mut())
     }
 }
+
+/// An owned output produced by calling `output_fn` on each call.
+pub fn owned_output<T: 'static>(
+    output_fn: impl Fn() -> T + Send + Sync + 'static,
+) -> crate::output::owning::Owned<T> {
+    crate::output::owning::Owned(Box::new(move || Some(output_fn())))
+}
+
+/// Turns the items of a mocked `Pin<Box<dyn Stream>>` output into a stream.
+///
+/// It is only a stream with the `futures-core-0-3` feature.
+pub struct IterStream<I>(I);
+
+impl<I: Iterator + Unpin> IterStream<I> {
+    pub fn new(items: impl IntoIterator<IntoIter = I>) -> Self {
+        Self(items.into_iter())
+    }
+}
+
+/// Turns the items of a mocked `Pin<Box<dyn Stream>>` output into the pinned stream.
+#[cfg(feature = "futures-core-0-3")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pinned_item_stream {
+    ($items:expr) => {
+        $crate::alloc::Box::pin($crate::private::IterStream::new($items))
+    };
+}
+
+/// Mocked `Pin<Box<dyn Stream>>` outputs can't be produced without the `futures-core-0-3` feature.
+#[cfg(not(feature = "futures-core-0-3"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pinned_item_stream {
+    ($items:expr) => {
+        ::core::compile_error!(
+            "unimock: methods returning `Pin<Box<dyn Stream>>` can only be mocked with the `futures-core-0-3` feature"
+        )
+    };
+}
+
+pub use crate::__pinned_item_stream as pinned_item_stream;
+
+#[cfg(feature = "futures-core-0-3")]
+impl<I: Iterator + Unpin> futures_core_0_3::Stream for IterStream<I> {
+    type Item = I::Item;
+
+    fn poll_next(
+        mut self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<Self::Item>> {
+        core::task::Poll::Ready(self.0.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
@@ -1,52 +1,239 @@
 use core::any::TypeId;
 use core::sync::atomic::AtomicUsize;
 
-use crate::alloc::{vec, BTreeMap, Vec};
+use once_cell::sync::OnceCell;
+
+use crate::alloc::{format, vec, BTreeMap, Box, String, Vec};
+use crate::assemble::MockAssembler;
 use crate::debug;
 use crate::error;
+use crate::factory::TypeFactory;
 use crate::fn_mocker::{FnMocker, PatternMatchMode};
 use crate::private::MutexIsh;
-use crate::FallbackMode;
+use crate::wait::CallNotifier;
+use crate::{Clause, FallbackMode};
+
+/// The mockers assembled from one clause.
+///
+/// The first layer comes from the clause the [Unimock](crate::Unimock) was created with, and later layers are added by [Unimock::extend](crate::Unimock::extend).
+/// Layers are never removed or modified after being added, which allows retaining shared references into them.
+pub(crate) struct Layer {
+    pub fn_mockers: BTreeMap<TypeId, FnMocker>,
+    pub type_factories: BTreeMap<TypeId, TypeFactory>,
+    /// The end of the ordered call indexes of the call patterns in this layer and the layers before it.
+    ordered_call_end: usize,
+    next: OnceCell<Box<Layer>>,
+}
+
+impl Layer {
+    pub fn new(
+        fn_mockers: BTreeMap<TypeId, FnMocker>,
+        type_factories: BTreeMap<TypeId, TypeFactory>,
+        ordered_call_end: usize,
+    ) -> Self {
+        Self {
+            fn_mockers,
+            type_factories,
+            ordered_call_end,
+            next: OnceCell::new(),
+        }
+    }
+}
 
 pub(crate) struct SharedState {
     pub fallback_mode: FallbackMode,
-    pub fn_mockers: BTreeMap<TypeId, FnMocker>,
+    root_layer: Layer,
+    extend_lock: MutexIsh<()>,
 
     #[cfg(feature = "std")]
     pub original_thread: std::thread::ThreadId,
 
+    pub policy: crate::Policy,
+
+    /// Ordered calls block until it is their turn, for at most this long.
+    #[cfg(feature = "std")]
+    pub call_order_timeout: Option<std::time::Duration>,
+
     next_ordered_call_index: AtomicUsize,
     pub panic_reasons: MutexIsh<Vec<error::MockError>>,
+    pub call_notifier: CallNotifier,
+    /// The number of [Unimock](crate::Unimock) instances sharing this state that are still alive.
+    #[cfg(feature = "std")]
+    pub live_instances: AtomicUsize,
+    /// The most recent ordered calls, for the [Timeline](debug::Timeline).
+    ordered_calls: MutexIsh<Vec<debug::OrderedCall>>,
 }
 
 impl SharedState {
-    pub fn new(fn_mockers: BTreeMap<TypeId, FnMocker>, fallback_mode: FallbackMode) -> Self {
+    pub fn new(root_layer: Layer, fallback_mode: FallbackMode) -> Self {
         Self {
             fallback_mode,
-            fn_mockers,
+            root_layer,
+            extend_lock: MutexIsh::new(()),
 
             #[cfg(feature = "std")]
             original_thread: std::thread::current().id(),
+            policy: crate::Policy::strict(),
+            #[cfg(feature = "std")]
+            call_order_timeout: None,
 
             next_ordered_call_index: AtomicUsize::new(0),
             panic_reasons: MutexIsh::new(vec![]),
+            call_notifier: CallNotifier::new(),
+            #[cfg(feature = "std")]
+            live_instances: AtomicUsize::new(1),
+            ordered_calls: MutexIsh::new(vec![]),
         }
     }
 
+    /// The layers of mockers, oldest first.
+    pub fn layers(&self) -> impl Iterator<Item = &Layer> + Clone {
+        core::iter::successors(Some(&self.root_layer), |layer| {
+            layer.next.get().map(|next| next.as_ref())
+        })
+    }
+
+    /// All mockers in all layers.
+    pub fn fn_mockers(&self) -> impl Iterator<Item = &FnMocker> {
+        self.layers()
+            .flat_map(|layer| layer.fn_mockers.values())
+    }
+
+    /// The mockers for one [MockFn](crate::MockFn) in all layers, oldest first.
+    pub fn fn_mockers_for(&self, type_id: TypeId) -> impl Iterator<Item = &FnMocker> + Clone {
+        self.layers()
+            .filter_map(move |layer| layer.fn_mockers.get(&type_id))
+    }
+
+    /// The newest type factory for a generic [MockFn](crate::MockFn) in all layers.
+    pub fn type_factory(&self, untyped_type_id: TypeId) -> Option<&TypeFactory> {
+        self.layers()
+            .filter_map(|layer| layer.type_factories.get(&untyped_type_id))
+            .last()
+    }
+
+    /// Verify the call counts and the call order so far, then start a new epoch:
+    /// The call counts verified here are not verified again, and the call order moves past the ordered call patterns set up so far.
+    ///
+    /// Mock fns that have not been called yet are left for the final verification.
+    pub fn checkpoint(&self) -> Result<(), Vec<error::MockError>> {
+        self.extend_lock.locked(|_| {
+            let result = crate::teardown::verify_shared_state(self, self.policy.allow_unused());
+
+            for fn_mocker in self.fn_mockers() {
+                fn_mocker.end_epoch();
+            }
+
+            let last_layer = self.layers().last().unwrap();
+            self.next_ordered_call_index.fetch_max(
+                last_layer.ordered_call_end,
+                core::sync::atomic::Ordering::SeqCst,
+            );
+
+            result
+        })
+    }
+
+    /// Add a layer with the mockers assembled from `clause`.
+    ///
+    /// Its ordered call patterns are placed after the ordered call patterns already set up.
+    #[track_caller]
+    pub fn extend(&self, clause: impl Clause) -> Result<(), String> {
+        self.extend_lock.locked(|_| {
+            let last_layer = self.layers().last().unwrap();
+            let layer =
+                MockAssembler::try_from_clause_at(clause, last_layer.ordered_call_end)?.finish();
+
+            for (type_id, fn_mocker) in layer.fn_mockers.iter() {
+                let Some(existing) = self.fn_mockers_for(*type_id).next() else {
+                    continue;
+                };
+                if existing.pattern_match_mode != fn_mocker.pattern_match_mode {
+                    return Err(format!(
+                        "A clause for {path} has already been registered as {old_mode:?}, but got re-registered as {new_mode:?}. They cannot be mixed for the same MockFn.",
+                        path = &fn_mocker.info.path,
+                        old_mode = existing.pattern_match_mode,
+                        new_mode = fn_mocker.pattern_match_mode,
+                    ));
+                }
+            }
+
+            if last_layer.next.set(Box::new(layer)).is_err() {
+                panic!("BUG: Layer added without holding the extend lock.");
+            }
+
+            Ok(())
+        })
+    }
+
     pub fn bump_ordered_call_index(&self) -> usize {
         self.next_ordered_call_index
             .fetch_add(1, core::sync::atomic::Ordering::SeqCst)
     }
 
-    pub fn clone_panic_reasons(&self) -> Vec<error::MockError> {
-        self.panic_reasons.locked(|reasons| reasons.clone())
+    /// The index of the next ordered call, without claiming it.
+    #[cfg(feature = "std")]
+    pub fn peek_ordered_call_index(&self) -> usize {
+        self.next_ordered_call_index
+            .load(core::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Claim the ordered call `index`, if no other call claimed it first.
+    #[cfg(feature = "std")]
+    pub fn claim_ordered_call_index(&self, index: usize) -> bool {
+        self.next_ordered_call_index
+            .compare_exchange(
+                index,
+                index + 1,
+                core::sync::atomic::Ordering::SeqCst,
+                core::sync::atomic::Ordering::SeqCst,
+            )
+            .is_ok()
+    }
+
+    /// Whether any ordered call pattern expects the ordered call `index`.
+    #[cfg(feature = "std")]
+    pub fn has_ordered_call_pattern(&self, index: usize) -> bool {
+        self.fn_mockers().any(|fn_mocker| {
+            fn_mocker.pattern_match_mode == PatternMatchMode::InOrder
+                && fn_mocker.find_call_pattern_for_call_order(index).is_some()
+        })
+    }
+
+    /// Record an ordered call for the timeline, forgetting the oldest one when there are more than [debug::Timeline::MAX_CALLS].
+    pub fn record_ordered_call(&self, ordered_call_index: usize, fn_call: debug::FnActualCall) {
+        self.ordered_calls.locked(|calls| {
+            // Calls from other threads may be recorded slightly out of order
+            let position = calls.partition_point(|call| call.index < ordered_call_index);
+            calls.insert(
+                position,
+                debug::OrderedCall {
+                    index: ordered_call_index,
+                    fn_call,
+                },
+            );
+
+            if calls.len() > debug::Timeline::MAX_CALLS {
+                calls.remove(0);
+            }
+        });
+    }
+
+    pub fn timeline(&self) -> debug::Timeline {
+        self.ordered_calls
+            .locked(|calls| debug::Timeline::new(calls.as_slice().into()))
+    }
+
+    /// Take the errors that made calls panic, so they are only reported once.
+    pub fn take_panic_reasons(&self) -> Vec<error::MockError> {
+        self.panic_reasons.locked(core::mem::take)
     }
 
     pub fn find_ordered_expected_call_pattern_debug(
         &self,
         ordered_call_index: usize,
     ) -> Option<debug::CallPatternDebug> {
-        self.fn_mockers.values().find_map(|fn_mocker| {
+        self.fn_mockers().find_map(|fn_mocker| {
             if fn_mocker.pattern_match_mode != PatternMatchMode::InOrder {
                 return None;
             }
@@ -1,14 +1,27 @@
 use crate::alloc::{Arc, ToString, Vec};
-use crate::{error::MockError, Unimock};
+use crate::state::SharedState;
+use crate::{error::MockError, Policy, Unimock};
 
 #[track_caller]
 pub(crate) fn teardown_panic(unimock: &mut Unimock) {
     if let Err(errors) = teardown(unimock) {
-        let error_strings = errors
-            .iter()
-            .map(<MockError as ToString>::to_string)
-            .collect::<Vec<_>>();
-        panic!("{}", error_strings.join("\n"));
+        panic_with_errors(None, errors);
+    }
+}
+
+/// Panic with one line per error, after the `heading` line if there is one.
+#[track_caller]
+pub(crate) fn panic_with_errors(
+    heading: Option<core::fmt::Arguments<'_>>,
+    errors: Vec<MockError>,
+) -> ! {
+    let error_strings = errors
+        .iter()
+        .map(<MockError as ToString>::to_string)
+        .collect::<Vec<_>>();
+    match heading {
+        Some(heading) => panic!("{heading}\n{}", error_strings.join("\n")),
+        None => panic!("{}", error_strings.join("\n")),
     }
 }
 
@@ -64,18 +77,31 @@ pub(crate) fn teardown(unimock: &mut Unimock) -> Result<(), Vec<MockError>> {
         panic!("Original Unimock instance destroyed on a different thread than the one it was created on. To solve this, clone the object before sending it to the other thread.");
     }
 
+    verify_shared_state(&unimock.shared_state, unimock.shared_state.policy)
+}
+
+/// Verify the calls made by all instances sharing the state according to `policy`.
+pub(crate) fn verify_shared_state(
+    shared_state: &SharedState,
+    policy: Policy,
+) -> Result<(), Vec<MockError>> {
     {
         // if already in error state, it must be from another thread. Forward those errors to the original thread.
         // (if original is even still in the original thread.. But report as close to the test "root" as possible)
-        let panic_reasons = unimock.shared_state.clone_panic_reasons();
+        let panic_reasons = shared_state.take_panic_reasons();
         if !panic_reasons.is_empty() {
             return Err(panic_reasons);
         }
     }
 
     let mut mock_errors = Vec::new();
-    for (_, fn_mocker) in unimock.shared_state.fn_mockers.iter() {
-        fn_mocker.verify(&mut mock_errors);
+    for fn_mocker in shared_state.fn_mockers() {
+        fn_mocker.verify(&policy, &mut mock_errors);
+    }
+
+    // forwarded panic reasons were logged when they were raised, so only log these
+    for error in mock_errors.iter() {
+        error.log();
     }
 
     if mock_errors.is_empty() {
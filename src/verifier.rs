@@ -0,0 +1,65 @@
+//! Verification of a [Unimock](crate::Unimock) instance that has been moved away from the test thread.
+
+use core::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::alloc::Arc;
+use crate::state::SharedState;
+use crate::{teardown, wait};
+
+/// Handle for verifying the calls of a [Unimock](crate::Unimock) instance after all its clones have been dropped, on any thread.
+///
+/// Created with [Unimock::verifier](crate::Unimock::verifier).
+/// Like the original instance, a verifier that is dropped without calling [wait_and_verify](Verifier::wait_and_verify) verifies right away,
+/// and panics if any instance is still alive at that point.
+#[must_use = "calls are not verified unless the verifier is used"]
+pub struct Verifier {
+    shared_state: Arc<SharedState>,
+    verified: bool,
+}
+
+impl Verifier {
+    pub(crate) fn new(shared_state: Arc<SharedState>) -> Self {
+        Self {
+            shared_state,
+            verified: false,
+        }
+    }
+
+    /// Wait until every instance sharing the mock setup has been dropped, then verify their calls.
+    ///
+    /// Panics if the instances are still alive after `timeout`, or if verification fails.
+    #[track_caller]
+    pub fn wait_and_verify(mut self, timeout: Duration) {
+        self.verified = true;
+
+        if let Err(alive) = wait::wait_for_drop(&self.shared_state, timeout) {
+            panic!("Unimock verifier timed out after {timeout:?}, while {alive} instance(s) were still alive.");
+        }
+
+        self.verify();
+    }
+
+    fn verify(&self) {
+        if let Err(errors) =
+            teardown::verify_shared_state(&self.shared_state, self.shared_state.policy)
+        {
+            teardown::panic_with_errors(None, errors);
+        }
+    }
+}
+
+impl Drop for Verifier {
+    fn drop(&mut self) {
+        if self.verified || std::thread::panicking() {
+            return;
+        }
+
+        let alive = self.shared_state.live_instances.load(Ordering::SeqCst);
+        if alive > 0 {
+            panic!("Unimock verifier dropped while {alive} instance(s) were still alive. Use wait_and_verify to wait for them.");
+        }
+
+        self.verify();
+    }
+}
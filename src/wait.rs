@@ -0,0 +1,269 @@
+//! Waiting for calls made on other threads or tasks.
+
+use core::any::TypeId;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use crate::alloc::Vec;
+use crate::private::MutexIsh;
+use crate::state::SharedState;
+use crate::{MockFn, Unimock};
+
+/// Notifies waiters each time a call is matched by a call pattern.
+pub(crate) struct CallNotifier {
+    #[cfg(feature = "std")]
+    generation: std::sync::Mutex<u64>,
+    #[cfg(feature = "std")]
+    condvar: std::sync::Condvar,
+    /// The waker of each pending [UntilCalled] future, keyed by the future.
+    wakers: MutexIsh<Vec<(usize, Waker)>>,
+    next_waker_key: AtomicUsize,
+}
+
+impl CallNotifier {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            generation: std::sync::Mutex::new(0),
+            #[cfg(feature = "std")]
+            condvar: std::sync::Condvar::new(),
+            wakers: MutexIsh::new(Vec::new()),
+            next_waker_key: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn notify(&self) {
+        #[cfg(feature = "std")]
+        {
+            *self.lock_generation() += 1;
+            self.condvar.notify_all();
+        }
+
+        for (_, waker) in self.wakers.locked(core::mem::take) {
+            waker.wake();
+        }
+    }
+
+    /// Register the waker of the future identified by `key`, replacing the one it registered before.
+    fn register_waker(&self, key: &mut Option<usize>, waker: &Waker) {
+        let key = *key.get_or_insert_with(|| self.next_waker_key.fetch_add(1, Ordering::Relaxed));
+
+        self.wakers.locked(|wakers| {
+            match wakers.iter_mut().find(|(registered, _)| *registered == key) {
+                Some((_, registered)) => {
+                    if !registered.will_wake(waker) {
+                        *registered = waker.clone();
+                    }
+                }
+                None => wakers.push((key, waker.clone())),
+            }
+        });
+    }
+
+    fn unregister_waker(&self, key: usize) {
+        self.wakers
+            .locked(|wakers| wakers.retain(|(registered, _)| *registered != key));
+    }
+
+    /// Lock the generation counter, ignoring poisoning by a waiter that panicked on timeout.
+    #[cfg(feature = "std")]
+    fn lock_generation(&self) -> std::sync::MutexGuard<'_, u64> {
+        self.generation
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    #[cfg(feature = "std")]
+    fn wait_timeout<'a>(
+        &self,
+        generation: std::sync::MutexGuard<'a, u64>,
+        timeout: std::time::Duration,
+    ) -> std::sync::MutexGuard<'a, u64> {
+        match self.condvar.wait_timeout(generation, timeout) {
+            Ok((generation, _)) => generation,
+            Err(poisoned) => poisoned.into_inner().0,
+        }
+    }
+}
+
+fn actual_calls(shared_state: &SharedState, type_id: TypeId) -> Option<usize> {
+    let mut fn_mockers = shared_state.fn_mockers_for(type_id).peekable();
+    fn_mockers.peek()?;
+
+    Some(fn_mockers.map(|fn_mocker| fn_mocker.actual_calls()).sum())
+}
+
+#[track_caller]
+fn counted_calls<F: MockFn>(shared_state: &SharedState) -> usize {
+    match actual_calls(shared_state, TypeId::of::<F>()) {
+        Some(calls) => calls,
+        None => panic!(
+            "{}: Cannot wait for calls, because there are no call patterns that count them.",
+            F::info().path
+        ),
+    }
+}
+
+#[cfg(feature = "std")]
+#[track_caller]
+pub(crate) fn wait_for<F: MockFn>(
+    shared_state: &SharedState,
+    times: usize,
+    timeout: std::time::Duration,
+) {
+    use crate::counter::NCalls;
+
+    let deadline = std::time::Instant::now() + timeout;
+    let notifier = &shared_state.call_notifier;
+    let mut generation = notifier.lock_generation();
+
+    loop {
+        let calls = counted_calls::<F>(shared_state);
+        if calls >= times {
+            return;
+        }
+
+        let now = std::time::Instant::now();
+        if now >= deadline {
+            panic!(
+                "{}: Timed out after {timeout:?} waiting for it to match {}, but it matched {}.",
+                F::info().path,
+                NCalls(times),
+                NCalls(calls)
+            );
+        }
+
+        generation = notifier.wait_timeout(generation, deadline - now);
+    }
+}
+
+/// Block until every instance sharing `shared_state` has been dropped.
+///
+/// Returns the number of instances still alive on timeout.
+#[cfg(feature = "std")]
+pub(crate) fn wait_for_drop(
+    shared_state: &SharedState,
+    timeout: std::time::Duration,
+) -> Result<(), usize> {
+    use core::sync::atomic::Ordering;
+
+    let deadline = std::time::Instant::now() + timeout;
+    let notifier = &shared_state.call_notifier;
+    let mut generation = notifier.lock_generation();
+
+    loop {
+        let alive = shared_state.live_instances.load(Ordering::SeqCst);
+        if alive == 0 {
+            return Ok(());
+        }
+
+        let now = std::time::Instant::now();
+        if now >= deadline {
+            return Err(alive);
+        }
+
+        generation = notifier.wait_timeout(generation, deadline - now);
+    }
+}
+
+/// Whether an ordered call is the one expected at some position in the call order.
+#[cfg(feature = "std")]
+pub(crate) enum Turn {
+    Mine,
+    NotYet,
+    /// No call pattern expects any call at this position.
+    Never,
+}
+
+/// Block until `turn` says it is the caller's turn in the call order, and claim that position.
+///
+/// Returns [None] on timeout.
+#[cfg(feature = "std")]
+pub(crate) fn wait_for_turn(
+    shared_state: &SharedState,
+    timeout: std::time::Duration,
+    turn: impl Fn(usize) -> Turn,
+) -> Option<usize> {
+    let deadline = std::time::Instant::now() + timeout;
+    let notifier = &shared_state.call_notifier;
+    let mut generation = notifier.lock_generation();
+
+    loop {
+        let index = shared_state.peek_ordered_call_index();
+        match turn(index) {
+            Turn::Mine | Turn::Never => {
+                if shared_state.claim_ordered_call_index(index) {
+                    return Some(index);
+                }
+                continue;
+            }
+            Turn::NotYet => {}
+        }
+
+        let now = std::time::Instant::now();
+        if now >= deadline {
+            return None;
+        }
+
+        generation = notifier.wait_timeout(generation, deadline - now);
+    }
+}
+
+/// Future returned from [Unimock::until_called], which completes when a [MockFn] has been called a number of times.
+#[must_use = "futures do nothing unless polled"]
+pub struct UntilCalled<'u, F> {
+    unimock: &'u Unimock,
+    times: usize,
+    /// Identifies the waker this future registered with the [CallNotifier].
+    waker_key: Option<usize>,
+    mock_fn: core::marker::PhantomData<F>,
+}
+
+impl<'u, F> UntilCalled<'u, F> {
+    pub(crate) fn new(unimock: &'u Unimock, times: usize) -> Self {
+        Self {
+            unimock,
+            times,
+            waker_key: None,
+            mock_fn: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<F: MockFn> Future for UntilCalled<'_, F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        let shared_state = &this.unimock.shared_state;
+        if counted_calls::<F>(shared_state) >= this.times {
+            return Poll::Ready(());
+        }
+
+        shared_state
+            .call_notifier
+            .register_waker(&mut this.waker_key, cx.waker());
+
+        // A call may have been counted before the waker was registered
+        if counted_calls::<F>(shared_state) >= this.times {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<F> Drop for UntilCalled<'_, F> {
+    fn drop(&mut self) {
+        if let Some(key) = self.waker_key {
+            self.unimock
+                .shared_state
+                .call_notifier
+                .unregister_waker(key);
+        }
+    }
+}
+
+impl<F> Unpin for UntilCalled<'_, F> {}
@@ -0,0 +1,10 @@
+//! A trait exported from a crate that is neither unimock nor the crate that mocks it.
+
+#[unimock_macros::export]
+pub trait Store {
+    fn load(&self, key: &str) -> Option<String>;
+    fn store(&mut self, key: String, value: String);
+    fn load_or_empty(&self, key: &str) -> String {
+        self.load(key).unwrap_or_default()
+    }
+}
@@ -0,0 +1,57 @@
+use unimock::alloc::{vec, String, ToString, Vec};
+use unimock::*;
+
+#[unimock(api=UserRepoMock, api_style="PascalCase")]
+trait UserRepo {
+    fn get_user(&self, id: u32) -> String;
+    fn delete_user(&self, id: u32);
+}
+
+#[test]
+fn pascal_case_mock_fns() {
+    let u = Unimock::new((
+        UserRepoMock::GetUser
+            .next_call(matching!(1))
+            .returns("alice".to_string()),
+        UserRepoMock::DeleteUser.next_call(matching!(1)).returns(()),
+    ));
+
+    assert_eq!("alice", u.get_user(1));
+    u.delete_user(1);
+}
+
+#[unimock(api=TemplateMock, api_style="Mock{Method}")]
+trait Template {
+    fn r#type(&self) -> i32;
+}
+
+#[test]
+fn templated_mock_fns() {
+    let u = Unimock::new(TemplateMock::MockType.next_call(matching!()).returns(42));
+
+    assert_eq!(42, u.r#type());
+}
+
+#[unimock(api=ListedMock)]
+trait Listed {
+    fn a(&self);
+    fn b(&self) -> i32;
+    fn generic<T: 'static>(&self, t: T);
+}
+
+#[test]
+fn all_lists_the_mock_fns() {
+    let methods: Vec<_> = ListedMock::ALL
+        .iter()
+        .map(|info| {
+            let info = info();
+            (info.trait_ident(), info.method_ident())
+        })
+        .collect();
+
+    assert_eq!(
+        vec![("Listed", "a"), ("Listed", "b"), ("Listed", "generic")],
+        methods
+    );
+    assert_eq!(2, UserRepoMock::ALL.len());
+}
@@ -975,6 +975,88 @@ mod mutated_args {
         }
     }
 
+    #[test]
+    fn writes_arg() {
+        let u = Unimock::new(
+            Mut2Mock::mut2_a
+                .next_call(matching!(2, _, _))
+                .writes_arg::<1>(10)
+                .writes_arg::<2>(20)
+                .returns(30),
+        );
+
+        let mut b = 0;
+        let mut c = 0;
+        assert_eq!(30, u.mut2_b(2, &mut b, &mut c));
+        assert_eq!((10, 20), (b, c));
+    }
+
+    #[test]
+    fn writes_arg_each_call() {
+        let u = Unimock::new(
+            Mut1Mock::mut1_a
+                .each_call(matching!(_, _, _))
+                .writes_arg::<1>(42)
+                .returns(0),
+        );
+
+        let mut b = 0;
+        u.mut1_a(0, &mut b, 0);
+        assert_eq!(42, b);
+        b = 0;
+        u.mut1_a(0, &mut b, 0);
+        assert_eq!(42, b);
+    }
+
+    #[test]
+    #[should_panic(expected = "Mut1::mut1_a: writes_arg must be followed by a response.")]
+    fn writes_arg_without_response() {
+        Unimock::new(Mut1Mock::mut1_a.stub(|each| {
+            each.call(matching!(_, _, _)).returns(0);
+            each.call(matching!(_, _, _)).writes_arg::<1>(42);
+        }));
+    }
+
+    #[unimock(api = ReadBufMock)]
+    trait ReadBuf {
+        fn read_buf(&self, buf: &mut [u8]) -> usize;
+    }
+
+    #[test]
+    fn writes_arg_slice() {
+        let u = Unimock::new((
+            ReadBufMock::read_buf
+                .next_call(matching!(_))
+                .writes_arg::<0>(*b"abc")
+                .returns(3_usize),
+            ReadBufMock::read_buf
+                .next_call(matching!(_))
+                .writes_arg::<0>([1, 2])
+                .returns(2_usize),
+        ));
+
+        let mut buf = [0; 4];
+        assert_eq!(3, u.read_buf(&mut buf));
+        assert_eq!(b"abc\0", &buf);
+        assert_eq!(2, u.read_buf(&mut buf[..2]));
+        assert_eq!(&[1, 2, b'c', 0], &buf);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "ReadBuf::read_buf([0, 0]): Could not write argument of ReadBuf::read_buf(_) at tests/it/basic.rs:1052: The value has 3 elements, but the parameter only has room for 2."
+    )]
+    fn writes_arg_slice_longer_than_target() {
+        let u = Unimock::new(
+            ReadBufMock::read_buf
+                .next_call(matching!(_))
+                .writes_arg::<0>(*b"abc")
+                .returns(3_usize),
+        );
+
+        u.read_buf(&mut [0; 2]);
+    }
+
     pub struct InvariantLifetimeArg<'a> {
         _data: Option<&'a mut (dyn Any)>,
     }
@@ -1194,3 +1276,63 @@ mod mut_output {
         }
     }
 }
+
+mod answers_owned {
+    use super::*;
+
+    #[unimock(api=LookupMock)]
+    trait Lookup {
+        fn get(&self, key: &str) -> &str;
+        fn get_mut_self(&mut self, key: i32) -> &i32;
+        fn get_explicit<'a>(&'a self) -> &'a [u8];
+    }
+
+    #[test]
+    fn owned_values_are_lent() {
+        let mut u = Unimock::new((
+            LookupMock::get
+                .each_call(matching!(_))
+                .answers_owned(&|_, key| format!("v-{key}")),
+            LookupMock::get_mut_self
+                .next_call(matching!(_))
+                .answers_owned(&|_, key| key * 2),
+            LookupMock::get_explicit
+                .next_call(matching!())
+                .answers_owned(&|_| [1_u8, 2, 3]),
+        ));
+
+        assert_eq!("v-a", u.get("a"));
+        assert_eq!("v-b", u.get("b"));
+        assert_eq!(&42, u.get_mut_self(21));
+        assert_eq!(&[1, 2, 3], u.get_explicit());
+    }
+
+    #[unimock(api=FindMock)]
+    trait Find {
+        fn find(&self, key: i32) -> Option<&str>;
+        fn try_find(&self, key: i32) -> Result<&str, i32>;
+    }
+
+    #[test]
+    fn owned_values_are_lent_inside_option_and_result() {
+        let u = Unimock::new((
+            FindMock::find
+                .each_call(matching!(_))
+                .answers_owned(&|_, key| (key > 0).then(|| format!("v-{key}"))),
+            FindMock::try_find
+                .each_call(matching!(_))
+                .answers_owned(&|_, key| {
+                    if key > 0 {
+                        Ok(key.to_string())
+                    } else {
+                        Err(key)
+                    }
+                }),
+        ));
+
+        assert_eq!(Some("v-1"), u.find(1));
+        assert_eq!(None, u.find(0));
+        assert_eq!(Ok("2"), u.try_find(2));
+        assert_eq!(Err(-1), u.try_find(-1));
+    }
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use unimock::*;
+
+#[unimock(api=SequenceMock)]
+trait Sequence {
+    fn step(&self, n: i32);
+    fn done(&self);
+}
+
+#[test]
+fn threads_are_scheduled_in_call_order() {
+    let u = Unimock::new((
+        SequenceMock::step.next_call(matching!(0)).returns(()),
+        SequenceMock::step.next_call(matching!(1)).returns(()),
+        SequenceMock::step.next_call(matching!(2)).returns(()),
+        SequenceMock::step.next_call(matching!(3)).returns(()),
+        SequenceMock::done.next_call(matching!()).returns(()),
+    ))
+    .blocking_call_order(Duration::from_secs(10));
+
+    let handles: Vec<_> = (0..4)
+        .rev()
+        .map(|n| {
+            let u = u.clone();
+            std::thread::spawn(move || u.step(n))
+        })
+        .collect();
+
+    u.done();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "Sequence::step(1): Timed out after 10ms waiting for its turn in the call order. Expected a call matching Sequence::step(0) at tests/it/blocking_call_order.rs:43."
+)]
+fn call_times_out_waiting_for_its_turn() {
+    let u = Unimock::new((
+        SequenceMock::step.next_call(matching!(0)).returns(()),
+        SequenceMock::step.next_call(matching!(1)).returns(()),
+    ))
+    .blocking_call_order(Duration::from_millis(10));
+
+    u.step(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Sequence::step(1): Ordered call (2) out of range: There were no more ordered call patterns in line for selection."
+)]
+fn call_past_the_end_of_the_order_fails_without_waiting() {
+    let u = Unimock::new(SequenceMock::step.next_call(matching!(0)).returns(()))
+        .blocking_call_order(Duration::from_secs(3600));
+
+    u.step(0);
+    u.step(1);
+}
@@ -0,0 +1,94 @@
+use unimock::*;
+
+#[unimock(api=OrderedMock)]
+trait Ordered {
+    fn a(&self, arg: i32);
+    fn b(&self);
+}
+
+#[test]
+#[should_panic(expected = "Ordered::b() in thread 'worker': No mock implementation found.")]
+fn call_from_named_thread() {
+    let u = Unimock::new(());
+    let clone = u.clone();
+
+    let _ = std::thread::Builder::new()
+        .name("worker".to_string())
+        .spawn(move || clone.b())
+        .unwrap()
+        .join();
+}
+
+#[test]
+#[should_panic(
+    expected = "\nTimeline of ordered calls:\n  1. Ordered::a(1)\n  2. Ordered::a(3) in thread 'worker'"
+)]
+fn timeline_of_interleaved_ordered_calls() {
+    let u = Unimock::new((
+        OrderedMock::a.next_call(matching!(1)).returns(()),
+        OrderedMock::a.next_call(matching!(2)).returns(()),
+    ));
+    u.a(1);
+
+    let clone = u.clone();
+    let _ = std::thread::Builder::new()
+        .name("worker".to_string())
+        .spawn(move || clone.a(3))
+        .unwrap()
+        .join();
+}
+
+#[test]
+#[should_panic(
+    expected = "\nTimeline of ordered calls:\n  ...\n  10. Ordered::a(9)\n  11. Ordered::a(10)"
+)]
+fn timeline_keeps_the_most_recent_ordered_calls() {
+    let u = Unimock::new((
+        OrderedMock::a
+            .next_call(matching!(_))
+            .returns(())
+            .n_times(40),
+        OrderedMock::b.next_call(matching!()).returns(()),
+    ));
+    for arg in 0..40 {
+        u.a(arg);
+    }
+
+    let clone = u.clone();
+    let _ = std::thread::Builder::new()
+        .name("worker".to_string())
+        .spawn(move || clone.a(40))
+        .unwrap()
+        .join();
+}
+
+#[test]
+fn no_timeline_for_single_threaded_ordered_calls() {
+    let u = Unimock::new((
+        OrderedMock::a.next_call(matching!(1)).returns(()),
+        OrderedMock::a.next_call(matching!(2)).returns(()),
+    ))
+    .no_verify_in_drop();
+
+    u.a(1);
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| u.a(3))).unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(message.contains("Method invoked in the correct order (2), but inputs didn't match"));
+    assert!(!message.contains("Timeline"));
+}
+
+#[cfg(feature = "tokio-1-task-id")]
+#[test]
+#[should_panic(expected = "Ordered::b() in task ")]
+fn call_from_tokio_task() {
+    use crate::AsyncTest;
+
+    async {
+        let u = Unimock::new(());
+        let clone = u.clone();
+
+        let _ = tokio_1::spawn(async move { clone.b() }).await;
+    }
+    .test();
+}
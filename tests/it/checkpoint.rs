@@ -0,0 +1,151 @@
+use unimock::*;
+
+#[unimock(api=ValveMock)]
+trait Valve {
+    fn open(&self, amount: i32);
+    fn close(&self);
+}
+
+#[test]
+fn checkpoint_between_phases() {
+    let u = Unimock::new((
+        ValveMock::open.next_call(matching!(1)).returns(()),
+        ValveMock::close.next_call(matching!()).returns(()),
+    ));
+
+    u.open(1);
+    u.close();
+    u.checkpoint();
+
+    u.extend((
+        ValveMock::open.next_call(matching!(2)).returns(()),
+        ValveMock::close.next_call(matching!()).returns(()),
+    ));
+
+    u.open(2);
+    u.close();
+}
+
+#[test]
+#[should_panic(
+    expected = "Unimock checkpoint at tests/it/checkpoint.rs:41:7 failed:\nValve::close: Expected Valve::close() at tests/it/checkpoint.rs:36 to match exactly 1 call, but it actually matched no calls."
+)]
+fn checkpoint_reports_its_location() {
+    let u = Unimock::new((
+        ValveMock::open.next_call(matching!(1)).returns(()),
+        ValveMock::close.next_call(matching!()).returns(()),
+    ));
+
+    u.open(1);
+
+    u.checkpoint();
+}
+
+#[test]
+fn stubs_keep_answering_after_checkpoint() {
+    let u = Unimock::new(ValveMock::close.each_call(matching!()).returns(()));
+
+    u.close();
+    u.checkpoint();
+
+    u.close();
+}
+
+#[test]
+fn fixtures_keep_answering_after_checkpoint() {
+    let fixture = Fixture::new(|| {
+        (
+            ValveMock::open.each_call(matching!(_)).returns(()),
+            ValveMock::close.each_call(matching!()).returns(()),
+        )
+    });
+    let u = Unimock::new(fixture);
+
+    u.open(1);
+    u.checkpoint();
+
+    u.open(2);
+    u.close();
+}
+
+#[test]
+#[should_panic(expected = "Mock for Valve::open was never called. Dead mocks should be removed.")]
+fn dead_mocks_are_reported_by_the_final_verification() {
+    let u = Unimock::new((
+        ValveMock::open.each_call(matching!(_)).returns(()),
+        ValveMock::close.each_call(matching!()).returns(()),
+    ));
+
+    u.close();
+    u.checkpoint();
+}
+
+#[test]
+#[should_panic(
+    expected = "Valve::close: Expected Valve::close() at tests/it/checkpoint.rs:93 to match exactly 1 call, but it actually matched no calls."
+)]
+fn call_counts_after_checkpoint_are_verified_on_their_own() {
+    let u = Unimock::new(ValveMock::close.each_call(matching!()).returns(()).once());
+
+    u.close();
+    u.checkpoint();
+
+    u.extend(ValveMock::close.each_call(matching!()).returns(()).once());
+}
+
+#[test]
+fn exact_call_counts_start_over_in_each_phase() {
+    let u = Unimock::new(ValveMock::close.each_call(matching!()).returns(()).once());
+
+    u.close();
+    u.checkpoint();
+
+    u.extend(ValveMock::close.each_call(matching!()).returns(()).once());
+
+    u.close();
+}
+
+#[test]
+#[should_panic(expected = "Valve::close(): No matching call patterns.")]
+fn exact_call_patterns_stop_matching_after_checkpoint() {
+    let u = Unimock::new(ValveMock::close.each_call(matching!()).returns(()).once());
+
+    u.close();
+    u.checkpoint();
+
+    u.close();
+}
+
+#[test]
+#[should_panic(expected = "Mock for Valve::open was never called. Dead mocks should be removed.")]
+fn call_patterns_after_checkpoint_are_verified_on_drop() {
+    let u = Unimock::new(ValveMock::close.each_call(matching!()).returns(()));
+
+    u.close();
+    u.checkpoint();
+
+    u.extend(ValveMock::open.each_call(matching!(_)).returns(()));
+}
+
+#[test]
+fn checkpoint_follows_policy() {
+    let u = Unimock::new(ValveMock::close.each_call(matching!()).returns(()))
+        .with_policy(Policy::strict().allow_unused());
+
+    u.checkpoint();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn checkpoint_reports_errors_from_other_threads_once() {
+    let u = Unimock::new(ValveMock::close.each_call(matching!()).returns(())).no_verify_in_drop();
+
+    let clone = u.clone();
+    let _ = std::thread::spawn(move || clone.open(1)).join();
+
+    let first = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| u.checkpoint()));
+    assert!(first.is_err());
+
+    u.close();
+    u.checkpoint();
+}
@@ -0,0 +1,94 @@
+use unimock::alloc::String;
+use unimock::*;
+
+#[unimock(api=BufferMock)]
+trait Buffer<const N: usize> {
+    fn read(&self) -> [u8; N];
+}
+
+#[test]
+fn const_trait() {
+    let u = Unimock::new(
+        BufferMock::read
+            .with_types::<4>()
+            .some_call(matching!())
+            .returns([1, 2, 3, 4]),
+    );
+    assert_eq!([1, 2, 3, 4], <Unimock as Buffer<4>>::read(&u));
+}
+
+#[unimock(api=ParserMock)]
+trait Parser<'a> {
+    fn parse(&self, input: &'a str) -> &'a str;
+    fn len(&self, input: &'a str) -> usize;
+}
+
+#[test]
+fn lifetime_trait() {
+    let u = Unimock::new((
+        ParserMock::len.some_call(matching!("ab")).returns(2_usize),
+        ParserMock::parse
+            .some_call(matching!("ab"))
+            .answers(&|_, input| input),
+    ));
+    let s = String::from("ab");
+    assert_eq!(2, <Unimock as Parser<'_>>::len(&u, &s));
+    assert_eq!("ab", <Unimock as Parser<'_>>::parse(&u, &s));
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Token<'a>(&'a str);
+
+#[unimock(api=LexerMock)]
+trait Lexer<'a, 'b: 'a, T> {
+    fn next(&self, input: &'b str, extra: T) -> Option<Token<'a>>;
+    fn chunk<const M: usize>(&self, input: &'a [u8]) -> [u8; M];
+}
+
+#[unimock(api=MatrixMock)]
+trait Matrix<T: Copy, const N: usize> {
+    fn row(&self, index: usize) -> [T; N];
+}
+
+#[test]
+fn mixed() {
+    let u = Unimock::new((
+        LexerMock::next
+            .with_types::<i32>()
+            .some_call(matching!("x", 1))
+            .returns(Some(Token("tok"))),
+        LexerMock::chunk
+            .with_types::<i32, 2>()
+            .some_call(matching!(_))
+            .returns([7, 8]),
+        MatrixMock::row
+            .with_types::<u8, 3>()
+            .some_call(matching!(0))
+            .returns([1_u8, 2, 3]),
+    ));
+    assert_eq!(
+        Some(Token("tok")),
+        <Unimock as Lexer<'_, '_, i32>>::next(&u, "x", 1)
+    );
+    assert_eq!(
+        [7, 8],
+        <Unimock as Lexer<'_, '_, i32>>::chunk::<2>(&u, &[1, 2, 3])
+    );
+    assert_eq!([1, 2, 3], <Unimock as Matrix<u8, 3>>::row(&u, 0));
+}
+
+#[test]
+#[should_panic(
+    expected = "Buffer::read(): No mock implementation found for type arguments `<8>`. Mocked type arguments: `<4>`."
+)]
+fn unmocked_const_argument() {
+    let u = Unimock::new(
+        BufferMock::read
+            .with_types::<4>()
+            .some_call(matching!())
+            .returns([0; 4]),
+    )
+    .no_verify_in_drop();
+
+    <Unimock as Buffer<8>>::read(&u);
+}
@@ -0,0 +1,84 @@
+use unimock::alloc::{vec, String, ToString, Vec};
+use unimock::factory::TypeFactory;
+use unimock::*;
+
+#[unimock(api=StoreMock)]
+trait Store {
+    fn load(&self, key: &str) -> Option<String>;
+    fn save(&self, key: &str, value: String);
+    fn clear(&self);
+}
+
+#[unimock(api=ConfigMock)]
+trait Config {
+    fn get<T: 'static>(&self, key: &str) -> T;
+    fn name(&self) -> String;
+}
+
+fn method_idents(infos: &[MockFnInfo]) -> Vec<&'static str> {
+    infos.iter().map(MockFnInfo::method_ident).collect()
+}
+
+#[test]
+fn reports_never_stubbed_and_never_called_methods() {
+    let u = Unimock::new((
+        StoreMock::load
+            .some_call(matching!(_))
+            .returns(None::<String>),
+        StoreMock::save.some_call(matching!(_, _)).returns(()),
+    ))
+    // `save` is never called
+    .no_verify_in_drop();
+
+    u.load("a");
+
+    let report = u.coverage_report(&[StoreMock::ALL]);
+    assert_eq!(vec!["clear"], method_idents(report.never_stubbed()));
+    assert_eq!(vec!["save"], method_idents(report.never_called()));
+    assert!(!report.is_complete());
+}
+
+#[test]
+fn complete_coverage_of_several_traits() {
+    let u = Unimock::new((
+        StoreMock::clear.next_call(matching!()).returns(()),
+        ConfigMock::get
+            .with_types::<i32>()
+            .next_call(matching!("a"))
+            .returns(1),
+        ConfigMock::name
+            .next_call(matching!())
+            .returns("name".to_string()),
+    ));
+
+    u.clear();
+    assert_eq!(1, u.get::<i32>("a"));
+    u.name();
+
+    let report = u.coverage_report(&[&StoreMock::ALL[2..], ConfigMock::ALL]);
+    assert!(report.is_complete());
+    assert_eq!("", report.to_string());
+}
+
+#[test]
+fn type_factory_calls_count_as_coverage() {
+    let stubbed = Unimock::new((
+        ConfigMock::get.with_any_types(TypeFactory::new().default_of::<i32>()),
+        ConfigMock::name
+            .some_call(matching!())
+            .returns("name".to_string()),
+    ))
+    // `name` is never called
+    .no_verify_in_drop();
+
+    let report = stubbed.coverage_report(&[ConfigMock::ALL]);
+    assert_eq!(
+        "Config::get: stubbed, but never called\nConfig::name: stubbed, but never called\n",
+        report.to_string()
+    );
+
+    assert_eq!(0, stubbed.get::<i32>("a"));
+
+    let report = stubbed.coverage_report(&[ConfigMock::ALL]);
+    assert_eq!(vec!["name"], method_idents(report.never_called()));
+}
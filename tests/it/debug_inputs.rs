@@ -0,0 +1,67 @@
+use unimock::alloc::{format, String};
+use unimock::*;
+
+#[derive(PartialEq)]
+pub struct Packet {
+    id: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Header(u8);
+
+fn format_packet(packet: &Packet) -> String {
+    format!("Packet#{}", packet.id)
+}
+
+#[unimock(api=PerArgMock)]
+trait PerArg {
+    fn send(&self, #[unimock(debug_inputs = format_packet)] packet: Packet, header: Header);
+}
+
+#[test]
+#[should_panic(expected = "PerArg::send(Packet#2, Header(1)): No matching call patterns.")]
+fn per_arg_formatter() {
+    let u = Unimock::new(
+        PerArgMock::send
+            .some_call(matching!(Packet { id: 1 }, _))
+            .returns(()),
+    )
+    .no_verify_in_drop();
+
+    PerArg::send(&u, Packet { id: 2 }, Header(1));
+}
+
+#[unimock(api=FallbackMock, debug_inputs = unimock::input::type_summary)]
+trait Fallback {
+    fn send(&self, packet: Packet, header: &Header);
+}
+
+#[test]
+#[should_panic(
+    expected = "Fallback::send(<it::debug_inputs::Packet, 4 bytes>, Header(1)): No matching call patterns."
+)]
+fn trait_level_fallback_formatter() {
+    let u = Unimock::new(
+        FallbackMock::send
+            .some_call(matching!(Packet { id: 1 }, _))
+            .returns(()),
+    )
+    .no_verify_in_drop();
+
+    Fallback::send(&u, Packet { id: 2 }, &Header(1));
+}
+
+#[test]
+#[should_panic(
+    expected = "Equality mismatch for input #0:\nActual value did not equal expected value, but can't display the expected value because the type is likely missing #[derive(Debug)]. Actual value: Packet#2"
+)]
+fn mismatch_uses_formatter_for_actual_value() {
+    let u = Unimock::new(
+        PerArgMock::send
+            .next_call(matching!(eq!(&Packet { id: 1 }), _))
+            .returns(()),
+    )
+    .no_verify_in_drop();
+
+    PerArg::send(&u, Packet { id: 2 }, Header(1));
+}
@@ -102,7 +102,7 @@ fn should_crash_when_the_original_instance_disappears_before_the_clone() {
 
 #[cfg(feature = "std")]
 #[test]
-#[should_panic(expected = "SingleArg::method1(\"\"): No mock implementation found.")]
+#[should_panic(expected = "SingleArg::method1(\"\") in thread ThreadId(")]
 fn multithread_error_reporting_works() {
     let unimock = Unimock::new(());
 
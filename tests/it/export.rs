@@ -0,0 +1,117 @@
+use unimock::alloc::{String, ToString};
+use unimock::*;
+
+pub struct Value(pub i32);
+
+#[unimock::export]
+pub trait Exported {
+    fn load(&self, key: &str) -> Option<String>;
+    fn value(&self) -> crate::export::Value;
+    fn or_default(&self, key: &str) -> String {
+        self.load(key).unwrap_or_default()
+    }
+}
+
+mod mocked {
+    use super::*;
+
+    unimock::mock_trait!(Exported, api = ExportedMock);
+
+    #[test]
+    fn mock_exported_trait() {
+        let u = Unimock::new((
+            ExportedMock::load
+                .next_call(matching!("a"))
+                .returns(Some("value".to_string())),
+            ExportedMock::value
+                .next_call(matching!())
+                .returns(Value(42)),
+        ));
+
+        assert_eq!(Some("value".to_string()), u.load("a"));
+        assert_eq!(42, u.value().0);
+    }
+
+    #[test]
+    fn default_impl_of_exported_trait() {
+        let u = Unimock::new(
+            ExportedMock::load
+                .next_call(matching!("b"))
+                .returns(None::<String>),
+        );
+
+        assert_eq!("", u.or_default("b"));
+    }
+}
+
+mod crate_path {
+    use super::*;
+
+    unimock::mock_trait!(
+        crate::export::Exported,
+        api = CratePathMock,
+        standalone = CratePathExported
+    );
+
+    #[test]
+    fn mock_exported_trait_by_crate_path() {
+        let u = CratePathExported(Unimock::new(
+            CratePathMock::load
+                .next_call(matching!("a"))
+                .returns(Some("value".to_string())),
+        ));
+
+        assert_eq!(Some("value".to_string()), u.load("a"));
+    }
+}
+
+mod self_path {
+    use super::*;
+
+    unimock::mock_trait!(
+        self::Exported,
+        api = SelfPathMock,
+        standalone = SelfPathExported
+    );
+
+    #[test]
+    fn mock_exported_trait_by_self_path() {
+        let u = SelfPathExported(Unimock::new(
+            SelfPathMock::or_default
+                .next_call(matching!("b"))
+                .returns("default".to_string()),
+        ));
+
+        assert_eq!("default", u.or_default("b"));
+    }
+}
+
+mod third_crate {
+    use super::*;
+    use unimock_test_exporter::Store;
+
+    unimock::mock_trait!(
+        unimock_test_exporter::Store,
+        api = StoreMock,
+        standalone = MockStore
+    );
+
+    #[test]
+    fn mock_trait_from_third_crate() {
+        let mut store = MockStore(Unimock::new((
+            StoreMock::store
+                .next_call(matching!("a", "value"))
+                .returns(()),
+            StoreMock::load
+                .next_call(matching!("a"))
+                .returns(Some("value".to_string())),
+            StoreMock::load_or_empty
+                .next_call(matching!("b"))
+                .returns("".to_string()),
+        )));
+
+        store.store("a".to_string(), "value".to_string());
+        assert_eq!(Some("value".to_string()), store.load("a"));
+        assert_eq!("", store.load_or_empty("b"));
+    }
+}
@@ -0,0 +1,89 @@
+use unimock::*;
+
+#[unimock(api=CounterMock)]
+trait Counter {
+    fn get(&self, key: i32) -> i32;
+    fn reset(&self);
+}
+
+#[test]
+fn extend_continues_the_call_order() {
+    let u = Unimock::new((
+        CounterMock::get.next_call(matching!(1)).returns(1),
+        CounterMock::reset.next_call(matching!()).returns(()),
+    ));
+
+    assert_eq!(1, u.get(1));
+    u.reset();
+
+    u.extend((
+        CounterMock::get.next_call(matching!(1)).returns(0),
+        CounterMock::reset.next_call(matching!()).returns(()),
+    ));
+
+    assert_eq!(0, u.get(1));
+    u.reset();
+}
+
+#[test]
+fn newest_call_patterns_take_precedence() {
+    let u = Unimock::new(CounterMock::get.each_call(matching!(_)).returns(0));
+
+    assert_eq!(0, u.get(1));
+
+    u.extend(CounterMock::get.each_call(matching!(1)).returns(1));
+
+    assert_eq!(1, u.get(1));
+    assert_eq!(0, u.get(2));
+}
+
+#[test]
+fn extend_through_a_clone() {
+    let u = Unimock::new(CounterMock::get.each_call(matching!(_)).returns(0));
+    let clone = u.clone();
+
+    assert_eq!(0, u.get(1));
+    clone.extend(CounterMock::reset.each_call(matching!()).returns(()));
+
+    u.reset();
+}
+
+#[test]
+#[should_panic(
+    expected = "A clause for Counter::get has already been registered as InAnyOrder, but got re-registered as InOrder. They cannot be mixed for the same MockFn."
+)]
+fn extend_cannot_mix_call_order_modes() {
+    let u = Unimock::new(CounterMock::get.each_call(matching!(_)).returns(0)).no_verify_in_drop();
+
+    u.extend(CounterMock::get.next_call(matching!(_)).returns(0));
+}
+
+#[test]
+#[should_panic(
+    expected = "Mock for Counter::reset was never called. Dead mocks should be removed."
+)]
+fn extension_is_verified() {
+    let u = Unimock::new(CounterMock::get.each_call(matching!(_)).returns(0));
+
+    assert_eq!(0, u.get(1));
+    u.extend(CounterMock::reset.each_call(matching!()).returns(()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn mismatches_are_numbered_across_layers() {
+    let u = Unimock::new(CounterMock::get.each_call(matching!(1)).returns(1)).no_verify_in_drop();
+    u.extend(CounterMock::get.each_call(matching!(2)).returns(2));
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| u.get(3))).unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(
+        message.contains("mismatch for call pattern #0"),
+        "{message}"
+    );
+    assert!(
+        message.contains("mismatch for call pattern #1"),
+        "{message}"
+    );
+}
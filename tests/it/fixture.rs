@@ -0,0 +1,107 @@
+use unimock::*;
+
+#[unimock(api=ServiceMock)]
+trait Service {
+    fn lookup(&self, id: i32) -> i32;
+    fn store(&self, id: i32);
+    fn flush(&self);
+}
+
+fn base() -> Fixture {
+    Fixture::new(|| {
+        (
+            ServiceMock::lookup.each_call(matching!(_)).returns(0),
+            ServiceMock::store.each_call(matching!(_)).returns(()),
+        )
+    })
+}
+
+#[test]
+fn fixture_can_be_reused_and_leaves_unused_stubs_alone() {
+    let fixture = base();
+
+    let u = Unimock::new(fixture.clone());
+    assert_eq!(0, u.lookup(1));
+
+    let u = Unimock::new(fixture);
+    u.store(1);
+}
+
+#[test]
+fn override_takes_precedence_with_fixture_as_fallback() {
+    let u = Unimock::new(
+        base().override_with(|| ServiceMock::lookup.each_call(matching!(42)).returns(1)),
+    );
+
+    assert_eq!(1, u.lookup(42));
+    assert_eq!(0, u.lookup(7));
+}
+
+#[test]
+fn override_of_method_not_in_fixture() {
+    let u = Unimock::new(
+        base().override_with(|| ServiceMock::flush.next_call(matching!()).returns(())),
+    );
+
+    u.flush();
+}
+
+#[test]
+#[should_panic(
+    expected = "Mock for Service::flush was never called. Dead mocks should be removed."
+)]
+fn unused_override_is_a_dead_mock() {
+    let u = Unimock::new(
+        base().override_with(|| ServiceMock::flush.each_call(matching!()).returns(())),
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "Service::flush: Expected Service::flush() at tests/it/fixture.rs:64 to match exactly 1 call, but it actually matched no calls."
+)]
+fn fixture_call_counts_are_verified() {
+    let fixture = Fixture::new(|| ServiceMock::flush.each_call(matching!()).returns(()).once());
+
+    let u = Unimock::new(fixture);
+}
+
+#[test]
+fn ordered_override_keeps_its_position_in_the_call_order() {
+    let fixture = Fixture::new(|| {
+        (
+            ServiceMock::lookup.next_call(matching!(1)).returns(1),
+            ServiceMock::store.next_call(matching!(1)).returns(()),
+            ServiceMock::flush.next_call(matching!()).returns(()),
+        )
+    });
+
+    let u = Unimock::new(
+        fixture.override_with(|| ServiceMock::store.next_call(matching!(2)).returns(())),
+    );
+
+    assert_eq!(1, u.lookup(1));
+    u.store(2);
+    u.flush();
+}
+
+#[test]
+fn ordered_override_replaces_fixture_patterns() {
+    let u = Unimock::new(
+        base().override_with(|| ServiceMock::lookup.next_call(matching!(1)).returns(1)),
+    );
+
+    assert_eq!(1, u.lookup(1));
+}
+
+#[test]
+fn overridden_fixture_composes() {
+    let fixture = base().override_with(|| ServiceMock::lookup.each_call(matching!(42)).returns(1));
+    let u = Unimock::new(
+        fixture.override_with(|| ServiceMock::lookup.each_call(matching!(43)).returns(2)),
+    );
+
+    assert_eq!(2, u.lookup(43));
+    assert_eq!(1, u.lookup(42));
+    assert_eq!(0, u.lookup(7));
+}
@@ -73,6 +73,26 @@ mod param {
         deps.generic_param(42_i32);
     }
 
+    #[unimock(api=GenericMutParamMock)]
+    trait GenericMutParam<T> {
+        fn generic_mut_param(&self, param: &mut T);
+    }
+
+    #[test]
+    fn test_generic_mut_param_writes_arg() {
+        let deps = Unimock::new(
+            GenericMutParamMock::generic_mut_param
+                .with_types::<String>()
+                .next_call(matching!(_))
+                .writes_arg::<0>("written".to_string())
+                .returns(()),
+        );
+
+        let mut param = String::new();
+        deps.generic_mut_param(&mut param);
+        assert_eq!("written", param);
+    }
+
     #[unimock(api=GenericParamDebugMock)]
     trait GenericParamDebug<T: Debug> {
         fn generic_param_debug(&self, param: T) -> &'static str;
@@ -375,3 +395,80 @@ mod generics_and_associated_type {
         fn func<U: 'static>(&self) -> Option<Self::Assoc>;
     }
 }
+
+mod generic_return {
+    use super::*;
+    use unimock::factory::TypeFactory;
+
+    #[unimock(api=ConfigMock)]
+    trait Config {
+        fn get<T: 'static>(&self, key: &str) -> T;
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Config::get(\"key\"): No mock implementation found for type arguments `<i32>`. Mocked type arguments: `<alloc::string::String>`."
+    )]
+    fn wrong_type_names_requested_and_mocked_types() {
+        let u = Unimock::new(
+            ConfigMock::get
+                .with_types::<String>()
+                .each_call(matching!(_))
+                .returns("value".to_string()),
+        );
+
+        u.get::<i32>("key");
+    }
+
+    #[test]
+    fn any_type_factory() {
+        let u = Unimock::new(
+            ConfigMock::get.with_any_types(
+                TypeFactory::new()
+                    .with(|| "value".to_string())
+                    .default_of::<i32>(),
+            ),
+        );
+
+        assert_eq!("value", u.get::<String>("a"));
+        assert_eq!(0, u.get::<i32>("b"));
+        assert_eq!(0, u.get::<i32>("c"));
+    }
+
+    #[test]
+    fn typed_clauses_take_precedence_over_type_factory() {
+        let u = Unimock::new((
+            ConfigMock::get.with_any_types(TypeFactory::new().default_of::<i32>()),
+            ConfigMock::get
+                .with_types::<i32>()
+                .next_call(matching!("special"))
+                .returns(42),
+        ));
+
+        assert_eq!(42, u.get::<i32>("special"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Config::get(\"a\"): The type factory cannot produce a value of type `u8`. Produced types: `i32`."
+    )]
+    fn type_factory_missing_type() {
+        let u =
+            Unimock::new(ConfigMock::get.with_any_types(TypeFactory::new().default_of::<i32>()));
+
+        u.get::<u8>("a");
+    }
+
+    #[unimock(api=ViewMock)]
+    trait View<T> {
+        fn view(&self) -> &T;
+    }
+
+    #[test]
+    #[should_panic(expected = "View::view(): Type factories can only respond with owned outputs.")]
+    fn type_factory_unsupported_output() {
+        let u = Unimock::new(ViewMock::view.with_any_types(TypeFactory::new().default_of::<i32>()));
+
+        <Unimock as View<i32>>::view(&u);
+    }
+}
@@ -0,0 +1,132 @@
+#[rustversion::since(1.75)]
+mod rpit {
+    use unimock::alloc::{vec, Box, String, ToString, Vec};
+    use unimock::*;
+
+    use core::fmt::Display;
+
+    #[unimock(api = RpitMock)]
+    trait Rpit {
+        fn numbers(&self) -> impl Iterator<Item = u32>;
+        fn label(&self, id: i32) -> impl Display + Send;
+        fn numbers_mut(&mut self) -> impl Iterator<Item = u32>;
+        fn doubled(&self) -> impl Iterator<Item = u32> {
+            self.numbers().map(|n| n * 2)
+        }
+    }
+
+    #[test]
+    fn returns_any_value_implementing_the_trait() {
+        let mut u = Unimock::new((
+            RpitMock::numbers
+                .next_call(matching!())
+                .returns(vec![1, 2, 3].into_iter()),
+            RpitMock::label.each_call(matching!(_)).returns("label"),
+            RpitMock::numbers_mut.next_call(matching!()).returns(0..2),
+        ));
+
+        assert_eq!(vec![1, 2, 3], u.numbers().collect::<Vec<_>>());
+        assert_eq!("label", u.label(1).to_string());
+        assert_eq!("label", u.label(2).to_string());
+        assert_eq!(vec![0, 1], u.numbers_mut().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn answers_with_boxed_value() {
+        let u = Unimock::new(
+            RpitMock::label
+                .each_call(matching!(_))
+                .answers(&|_, id| Box::new(id * 2)),
+        );
+
+        assert_eq!("84", u.label(42).to_string());
+    }
+
+    #[test]
+    fn default_impl() {
+        let u = Unimock::new((
+            RpitMock::doubled
+                .next_call(matching!())
+                .applies_default_impl(),
+            RpitMock::numbers
+                .next_call(matching!())
+                .returns([1, 2].into_iter()),
+        ));
+
+        assert_eq!(vec![2, 4], u.doubled().collect::<Vec<_>>());
+    }
+
+    #[unimock(api = UnmockedMock, unmock_with=[unmocked_names])]
+    trait Unmocked {
+        fn names(&self) -> impl Iterator<Item = String>;
+    }
+
+    fn unmocked_names(_: &impl core::any::Any) -> impl Iterator<Item = String> {
+        ["real".to_string()].into_iter()
+    }
+
+    #[test]
+    fn unmock() {
+        let u = Unimock::new(
+            UnmockedMock::names
+                .next_call(matching!())
+                .applies_unmocked(),
+        );
+
+        assert_eq!(vec!["real".to_string()], u.names().collect::<Vec<_>>());
+    }
+
+    #[unimock(api = LocalMock, unmock_with=[local_names, _])]
+    trait Local {
+        fn local_names(&self) -> impl Iterator<Item = String>;
+        fn local_default(&self) -> impl Iterator<Item = u32> {
+            not_send_or_sync().map(|_| 1).chain([2])
+        }
+    }
+
+    fn not_send_or_sync() -> impl Iterator<Item = core::marker::PhantomData<*const ()>> {
+        core::iter::once(core::marker::PhantomData)
+    }
+
+    fn local_names(_: &impl core::any::Any) -> impl Iterator<Item = String> {
+        not_send_or_sync().map(|_| "real".to_string())
+    }
+
+    #[test]
+    fn outputs_without_auto_traits() {
+        let u = Unimock::new((
+            LocalMock::local_names
+                .next_call(matching!())
+                .applies_unmocked(),
+            LocalMock::local_default
+                .next_call(matching!())
+                .applies_default_impl(),
+        ));
+
+        assert_eq!(
+            vec!["real".to_string()],
+            u.local_names().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![1, 2], u.local_default().collect::<Vec<_>>());
+    }
+
+    #[unimock(api = GenericRpitMock)]
+    trait GenericRpit<T> {
+        fn items(&self) -> impl Iterator<Item = T>;
+    }
+
+    #[test]
+    fn generic() {
+        let u = Unimock::new(
+            GenericRpitMock::items
+                .with_types::<&'static str>()
+                .next_call(matching!())
+                .returns(["a", "b"].into_iter()),
+        );
+
+        assert_eq!(
+            vec!["a", "b"],
+            <Unimock as GenericRpit<&str>>::items(&u).collect::<Vec<_>>()
+        );
+    }
+}
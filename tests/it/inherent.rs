@@ -0,0 +1,100 @@
+use unimock::alloc::{format, String, ToString};
+use unimock::*;
+
+pub struct Client {
+    base_url: String,
+}
+
+#[unimock(api=ClientMock)]
+impl Client {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    pub fn set_base_url(&mut self, base_url: String) {
+        self.base_url = base_url;
+    }
+
+    pub fn with_base_url(&self, base_url: &str) -> Self {
+        Self::new(base_url)
+    }
+}
+
+mod service {
+    use super::MockableClient as Client;
+
+    use unimock::alloc::String;
+
+    pub fn fetch_user(client: &Client, id: u32) -> String {
+        client.get(if id == 0 { "/admin" } else { "/user" })
+    }
+}
+
+#[test]
+fn real_impl_is_kept() {
+    let mut client = Client::new("http://a");
+    assert_eq!("http://a/b", client.get("/b"));
+
+    client.set_base_url("http://c".to_string());
+    assert_eq!("http://c/d", client.with_base_url("http://c").get("/d"));
+}
+
+#[test]
+fn substitute_type_is_mocked() {
+    let client = UnimockClient::from(Unimock::new(
+        ClientMock::get
+            .next_call(matching!("/user"))
+            .returns("user"),
+    ));
+
+    assert_eq!("user", service::fetch_user(&client, 42));
+}
+
+#[test]
+fn mut_receiver_and_self_output() {
+    let mut client = UnimockClient(Unimock::new((
+        ClientMock::set_base_url
+            .next_call(matching!("x"))
+            .returns(()),
+        ClientMock::with_base_url
+            .next_call(matching!("y"))
+            .answers(&|u, _| UnimockClient(u.clone())),
+        ClientMock::get.next_call(matching!("/z")).returns("y/z"),
+    )));
+
+    client.set_base_url("x".to_string());
+    assert_eq!("y/z", client.with_base_url("y").get("/z"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn associated_fn_without_receiver() {
+    let u = Unimock::new((
+        ClientMock::new
+            .next_call(matching!("http://a"))
+            .answers(&|u, _| UnimockClient(u.clone())),
+        ClientMock::get.next_call(matching!("/b")).returns("mocked"),
+    ));
+    let _guard = u.enter();
+
+    let client = MockableClient::new("http://a");
+    assert_eq!("mocked", client.get("/b"));
+}
+
+#[test]
+#[should_panic(expected = "Client::get(\"/admin\"): No matching call patterns.")]
+fn errors_mention_the_type() {
+    let client = UnimockClient(Unimock::new(
+        ClientMock::get
+            .each_call(matching!("/user"))
+            .returns("user"),
+    ));
+
+    service::fetch_user(&client, 0);
+}
@@ -0,0 +1,109 @@
+use unimock::alloc::{vec, Box, String, ToString, Vec};
+use unimock::*;
+
+#[unimock(api=PagerMock)]
+trait Pager {
+    fn page(&self, index: usize) -> Box<dyn Iterator<Item = String>>;
+}
+
+#[test]
+fn boxed_iterator_returns_items() {
+    let u = Unimock::new((
+        PagerMock::page
+            .each_call(matching!(0))
+            .returns_items(["a".to_string(), "b".to_string()]),
+        PagerMock::page
+            .each_call(matching!(1))
+            .returns_items([] as [String; 0]),
+    ));
+
+    assert_eq!(vec!["a", "b"], u.page(0).collect::<Vec<_>>());
+    assert_eq!(2, u.page(0).count());
+    assert_eq!(0, u.page(1).count());
+}
+
+#[unimock(api=SharedPagerMock)]
+trait SharedPager {
+    fn shared_page(&self, index: usize) -> Box<dyn Iterator<Item = String> + Send + Sync>;
+}
+
+#[test]
+fn boxed_iterator_returns_boxed_iterator() {
+    let u = Unimock::new((
+        SharedPagerMock::shared_page
+            .next_call(matching!(0))
+            .returns(Box::new(vec!["c".to_string()].into_iter())
+                as Box<dyn Iterator<Item = String> + Send + Sync>),
+        SharedPagerMock::shared_page
+            .next_call(matching!(1))
+            .returns_items(["d".to_string()]),
+    ));
+
+    assert_eq!(vec!["c"], u.shared_page(0).collect::<Vec<_>>());
+    assert_eq!(vec!["d"], u.shared_page(1).collect::<Vec<_>>());
+}
+
+#[test]
+fn boxed_iterator_answers() {
+    let u = Unimock::new(
+        PagerMock::page
+            .next_call(matching!(_))
+            .answers(&|_, index| Box::new((0..index).map(|i| i.to_string()))),
+    );
+
+    assert_eq!(vec!["0", "1", "2"], u.page(3).collect::<Vec<_>>());
+}
+
+#[unimock(api=NumbersMock)]
+trait Numbers {
+    fn numbers(&self) -> Box<dyn Iterator<Item = &u32> + Send + '_>;
+}
+
+#[test]
+fn boxed_iterator_of_borrowed_items() {
+    let u = Unimock::new(
+        NumbersMock::numbers
+            .each_call(matching!())
+            .returns_items([1, 2, 3]),
+    );
+
+    assert_eq!(6_u32, u.numbers().sum());
+    assert_eq!(vec![&1, &2, &3], u.numbers().collect::<Vec<_>>());
+}
+
+#[cfg(all(feature = "futures-core-0-3", feature = "std"))]
+mod stream {
+    use super::*;
+    use crate::AsyncTest;
+
+    use core::pin::Pin;
+    use futures_core_0_3::Stream;
+
+    #[unimock(api=EventsMock)]
+    trait Events {
+        fn events(&self) -> Pin<Box<dyn Stream<Item = i32> + Send>>;
+    }
+
+    async fn collect(mut stream: Pin<Box<dyn Stream<Item = i32> + Send>>) -> Vec<i32> {
+        let mut items = vec![];
+        while let Some(item) = core::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            items.push(item);
+        }
+        items
+    }
+
+    #[test]
+    fn pinned_stream_returns_items() {
+        async {
+            let u = Unimock::new(
+                EventsMock::events
+                    .each_call(matching!())
+                    .returns_items([1, 2]),
+            );
+
+            assert_eq!(vec![1, 2], collect(u.events()).await);
+            assert_eq!(vec![1, 2], collect(u.events()).await);
+        }
+        .test()
+    }
+}
@@ -8,11 +8,36 @@ use core::future::Future;
 #[cfg(any(feature = "std", feature = "spin-lock"))]
 mod basic;
 
+mod api_style;
+
 mod arg_borrows;
 
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod checkpoint;
+
+mod const_generics;
+
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod coverage;
+
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod debug_inputs;
+
 mod default_impl;
 mod errors;
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod export;
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod extend;
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod fixture;
 mod generic;
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod impl_trait;
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod inherent;
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod items;
 mod matching_eq;
 mod test_debug;
 
@@ -27,15 +52,42 @@ mod mock_order;
 
 mod prefix;
 
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod standalone;
+
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod supertraits;
+
+#[cfg(any(feature = "std", feature = "spin-lock"))]
+mod tagged;
+
 #[cfg(all(feature = "pretty-print", any(feature = "std", feature = "spin-lock")))]
 mod pretty_mismatches;
 
 #[cfg(feature = "std")]
 mod async_fn;
 
+#[cfg(feature = "std")]
+mod blocking_call_order;
+
+#[cfg(feature = "std")]
+mod call_origin;
+
+#[cfg(feature = "std")]
+mod policy;
+
+#[cfg(feature = "std")]
+mod static_fn;
+
 #[cfg(all(feature = "mock-core", feature = "mock-std"))]
 mod std;
 
+#[cfg(feature = "std")]
+mod verifier;
+
+#[cfg(feature = "std")]
+mod wait;
+
 #[cfg(feature = "fragile")]
 mod test_fragile;
 
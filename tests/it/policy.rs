@@ -0,0 +1,127 @@
+use unimock::*;
+
+#[unimock(api=StoreMock)]
+trait Store {
+    fn get(&self, key: &str) -> Option<i32>;
+    fn put(&self, key: &str, value: i32);
+}
+
+fn fixture() -> impl Clause {
+    (
+        StoreMock::get.each_call(matching!(_)).returns(None),
+        StoreMock::put.each_call(matching!(_, _)).returns(()),
+    )
+}
+
+const CHILD_ENV: &str = "UNIMOCK_POLICY_TEST_CHILD";
+
+/// Run `scenario` in a child process that only runs the test `test_name`, and return what the child printed to stderr.
+///
+/// Returns [None] inside the child process, after running the scenario.
+fn stderr_of_child(test_name: &str, scenario: impl FnOnce()) -> Option<String> {
+    if std::env::var_os(CHILD_ENV).is_some() {
+        scenario();
+        return None;
+    }
+
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", test_name, "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    Some(String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+#[should_panic(expected = "Mock for Store::put was never called. Dead mocks should be removed.")]
+fn strict_policy_reports_unused_mocks() {
+    let u = Unimock::new(fixture()).with_policy(Policy::strict());
+
+    u.get("key");
+}
+
+#[test]
+fn allow_unused() {
+    let u = Unimock::new(fixture()).with_policy(Policy::strict().allow_unused());
+
+    assert_eq!(None, u.get("key"));
+}
+
+#[test]
+#[should_panic(
+    expected = "Store::put: Expected Store::put(_, _) at tests/it/policy.rs:57 to match exactly 1 call, but it actually matched no calls."
+)]
+fn allow_unused_still_verifies_explicit_call_counts() {
+    let u = Unimock::new(StoreMock::put.each_call(matching!(_, _)).returns(()).once())
+        .with_policy(Policy::strict().allow_unused());
+}
+
+#[test]
+fn extra_calls_as_warnings() {
+    let Some(stderr) = stderr_of_child("policy::extra_calls_as_warnings", || {
+        let u = Unimock::new(StoreMock::get.each_call(matching!(_)).returns(None).once())
+            .with_policy(Policy::strict().extra_calls_as_warnings());
+
+        u.get("a");
+        u.get("b");
+    }) else {
+        return;
+    };
+
+    assert!(
+        stderr.contains(
+            "unimock warning: Store::get: Expected Store::get(_) at tests/it/policy.rs:64 to match exactly 1 call, but it actually matched 2 calls."
+        ),
+        "{stderr}"
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "Store::get: Expected Store::get(_) at tests/it/policy.rs:88 to match exactly 2 calls, but it actually matched 1 call."
+)]
+fn extra_calls_as_warnings_still_fails_on_missing_calls() {
+    let u = Unimock::new(
+        StoreMock::get
+            .each_call(matching!(_))
+            .returns(None)
+            .n_times(2),
+    )
+    .with_policy(Policy::strict().extra_calls_as_warnings());
+
+    u.get("a");
+}
+
+#[test]
+fn report_on_drop() {
+    let Some(stderr) = stderr_of_child("policy::report_on_drop", || {
+        let _u = Unimock::new(fixture()).with_policy(Policy::strict().report_on_drop());
+    }) else {
+        return;
+    };
+
+    assert!(stderr.contains("unimock MockError: Mock for Store::get was never called. Dead mocks should be removed."), "{stderr}");
+}
+
+#[test]
+#[should_panic(expected = "Mock for Store::get was never called. Dead mocks should be removed.")]
+fn explicit_verify_panics_with_report_on_drop() {
+    let u = Unimock::new(fixture())
+        .with_policy(Policy::strict().report_on_drop())
+        .no_verify_in_drop();
+
+    u.verify();
+}
+
+#[test]
+#[should_panic(
+    expected = "Called with_policy() after the instance was cloned. Configure the instance before cloning it."
+)]
+fn configure_after_clone() {
+    let u = Unimock::new(()).no_verify_in_drop();
+    let _clone = u.clone();
+
+    let _ = u.with_policy(Policy::strict());
+}
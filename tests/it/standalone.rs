@@ -0,0 +1,90 @@
+use unimock::alloc::{String, ToString};
+use unimock::*;
+
+#[unimock(api=NameMock, standalone=NameStandalone)]
+trait Name {
+    fn name(&self) -> String;
+    fn rename(&mut self, name: &str);
+    fn greeting(&self) -> String {
+        "hello".to_string()
+    }
+}
+
+#[unimock(api=TitleMock, standalone=TitleStandalone)]
+trait Title {
+    fn name(&self) -> String;
+}
+
+fn greet(named: &impl Name) -> String {
+    named.name()
+}
+
+#[test]
+fn standalone_implements_only_its_trait() {
+    let standalone = NameStandalone::from(Unimock::new(
+        NameMock::name.next_call(matching!()).returns("name"),
+    ));
+
+    assert_eq!("name", greet(&standalone));
+}
+
+#[test]
+fn standalone_disambiguates_methods_with_equal_names() {
+    let name = NameStandalone(Unimock::new(
+        NameMock::name.next_call(matching!()).returns("name"),
+    ));
+    let title = TitleStandalone(Unimock::new(
+        TitleMock::name.next_call(matching!()).returns("title"),
+    ));
+
+    assert_eq!("name", name.name());
+    assert_eq!("title", title.name());
+}
+
+#[test]
+fn standalone_mut_receiver_and_default_impl() {
+    let mut standalone = NameStandalone(Unimock::new((
+        NameMock::rename.next_call(matching!("new")).returns(()),
+        NameMock::greeting
+            .next_call(matching!())
+            .applies_default_impl(),
+    )));
+
+    standalone.rename("new");
+    assert_eq!("hello", standalone.greeting());
+}
+
+#[unimock(api=AssocMock, standalone=AssocStandalone, type Item = i32; const SIZE: usize = 2;)]
+pub trait Assoc {
+    type Item;
+    const SIZE: usize;
+
+    fn item(&self) -> Self::Item;
+}
+
+#[test]
+fn standalone_associated_items() {
+    let standalone = AssocStandalone(Unimock::new(
+        AssocMock::item.next_call(matching!()).returns(7),
+    ));
+
+    assert_eq!(2, <AssocStandalone as Assoc>::SIZE);
+    assert_eq!(7, standalone.item());
+}
+
+#[unimock(api=GenericMock, standalone=GenericStandalone)]
+trait Generic<T> {
+    fn get(&self) -> T;
+}
+
+#[test]
+fn standalone_generic_trait() {
+    let standalone = GenericStandalone(Unimock::new(
+        GenericMock::get
+            .with_types::<i32>()
+            .next_call(matching!())
+            .returns(1),
+    ));
+
+    assert_eq!(1, <GenericStandalone as Generic<i32>>::get(&standalone));
+}
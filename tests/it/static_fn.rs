@@ -0,0 +1,139 @@
+use unimock::alloc::{String, ToString};
+use unimock::*;
+
+use crate::AsyncTest;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    port: u16,
+}
+
+#[unimock(api=ServerMock)]
+trait Server {
+    fn new(config: Config) -> Self;
+    fn default_port() -> u16;
+    fn name(&self) -> String;
+}
+
+fn start<S: Server>() -> S {
+    S::new(Config {
+        port: S::default_port(),
+    })
+}
+
+#[test]
+fn constructor_answers_with_the_current_instance() {
+    let u = Unimock::new((
+        ServerMock::default_port
+            .some_call(matching!())
+            .returns(8080_u16),
+        ServerMock::new
+            .some_call(matching!(Config { port: 8080 }))
+            .answers(&|u, _| u.clone()),
+        ServerMock::name.some_call(matching!()).returns("server"),
+    ));
+    let _guard = u.enter();
+
+    let server: Unimock = start();
+    assert_eq!("server", server.name());
+}
+
+#[test]
+fn nested_enter_restores_the_previous_instance() {
+    let outer = Unimock::new(
+        ServerMock::default_port
+            .each_call(matching!())
+            .returns(1_u16),
+    );
+    let inner = Unimock::new(
+        ServerMock::default_port
+            .some_call(matching!())
+            .returns(2_u16),
+    );
+
+    let _outer_guard = outer.enter();
+    assert_eq!(1, <Unimock as Server>::default_port());
+    {
+        let _inner_guard = inner.enter();
+        assert_eq!(2, <Unimock as Server>::default_port());
+    }
+    assert_eq!(1, <Unimock as Server>::default_port());
+}
+
+#[test]
+fn threads_have_separate_current_instances() {
+    let handles: Vec<_> = (0..4_u16)
+        .map(|port| {
+            std::thread::spawn(move || {
+                let u = Unimock::new(
+                    ServerMock::default_port
+                        .each_call(matching!())
+                        .returns(port),
+                );
+                let _guard = u.enter();
+                for _ in 0..10 {
+                    assert_eq!(port, <Unimock as Server>::default_port());
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+#[should_panic(expected = "Server::default_port: No current Unimock instance on this thread.")]
+fn panics_without_current_instance() {
+    <Unimock as Server>::default_port();
+}
+
+#[unimock(api=DefaultsMock, unmock_with=[real_version, _], standalone=DefaultsStandalone)]
+trait Defaults {
+    fn version() -> u32;
+    fn label(&self) -> String {
+        "label".to_string()
+    }
+}
+
+fn real_version() -> u32 {
+    1
+}
+
+#[test]
+fn static_fn_with_default_methods_and_unmock() {
+    let u = Unimock::new_partial(());
+    let _guard = u.enter();
+
+    assert_eq!(1, <Unimock as Defaults>::version());
+    assert_eq!("label", u.label());
+}
+
+#[unimock(api=AsyncServerMock)]
+trait AsyncServer {
+    async fn connect(url: String) -> Self;
+}
+
+#[test]
+fn async_static_fn() {
+    async {
+        let u = Unimock::new(
+            AsyncServerMock::connect
+                .some_call(matching!("url"))
+                .answers(&|u, _| u.clone()),
+        );
+        let _guard = u.enter();
+
+        let _connected = <Unimock as AsyncServer>::connect("url".to_string()).await;
+    }
+    .test()
+}
+
+#[test]
+fn standalone_delegates_static_fn() {
+    let u = Unimock::new(DefaultsMock::version.some_call(matching!()).returns(2_u32));
+    let _guard = u.enter();
+
+    assert_eq!(2, <DefaultsStandalone as Defaults>::version());
+}
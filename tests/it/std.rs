@@ -63,6 +63,21 @@ fn test_read() {
     assert_eq!("ok\n", line);
 }
 
+#[test]
+fn test_read_writes_arg() {
+    let mut reader = BufReader::new(Unimock::new(
+        ReadMock::read
+            .next_call(matching!(_))
+            .writes_arg::<0>(b"ok\n")
+            .returns(Ok(3)),
+    ));
+
+    let mut line = String::new();
+    let len = reader.read_line(&mut line).unwrap();
+    assert_eq!(len, 3);
+    assert_eq!("ok\n", line);
+}
+
 #[test]
 fn test_write() {
     let mut unimock = Unimock::new((
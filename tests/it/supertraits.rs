@@ -0,0 +1,129 @@
+use unimock::alloc::{String, ToString};
+use unimock::*;
+
+#[unimock::export]
+pub trait Base {
+    fn id(&self) -> u32;
+}
+
+#[unimock::export]
+pub trait Reader: Base {
+    fn read(&self, key: &str) -> Option<String>;
+}
+
+#[unimock::export]
+pub trait Writer: Send + Sync {
+    fn write(&self, key: &str, value: String);
+}
+
+#[unimock(api=RepoMock, with_supertraits)]
+pub trait Repo: Reader + Writer {
+    fn copy(&self, from: &str, to: &str);
+}
+
+fn copy_value(repo: &impl Repo, from: &str, to: &str) -> u32 {
+    if let Some(value) = repo.read(from) {
+        repo.write(to, value);
+    }
+    repo.id()
+}
+
+#[test]
+fn supertrait_mock_apis_are_nested_in_the_mock_module() {
+    let u = Unimock::new((
+        RepoMock::Reader::read
+            .next_call(matching!("a"))
+            .returns(Some("value".to_string())),
+        RepoMock::Writer::write
+            .next_call(matching!("b", "value"))
+            .returns(()),
+        RepoMock::Reader::Base::id
+            .next_call(matching!())
+            .returns(7_u32),
+    ));
+
+    assert_eq!(7, copy_value(&u, "a", "b"));
+}
+
+#[test]
+fn subtrait_methods_are_mocked_alongside_supertraits() {
+    let u = Unimock::new((
+        RepoMock::copy.next_call(matching!("a", "b")).returns(()),
+        RepoMock::Reader::read
+            .next_call(matching!("c"))
+            .returns(None::<String>),
+    ));
+
+    u.copy("a", "b");
+    assert_eq!(None, u.read("c"));
+}
+
+#[test]
+#[should_panic(expected = "Reader::read(\"a\"): No mock implementation found.")]
+fn supertrait_calls_are_reported_with_the_supertrait_name() {
+    Unimock::new(()).read("a");
+}
+
+mod diamond {
+    use super::*;
+
+    #[unimock::export]
+    pub trait Root {
+        fn root(&self) -> u32;
+    }
+
+    #[unimock::export]
+    pub trait Left: Root {
+        fn left(&self) -> u32;
+    }
+
+    #[unimock::export]
+    pub trait Right: Root {
+        fn right(&self) -> u32;
+    }
+
+    #[unimock(api=DiamondMock, with_supertraits)]
+    pub trait Diamond: Left + Right {}
+
+    #[test]
+    fn shared_supertrait_is_mocked_once() {
+        let u = Unimock::new((
+            DiamondMock::Left::Root::root
+                .next_call(matching!())
+                .returns(1_u32),
+            DiamondMock::Right::Root::root
+                .next_call(matching!())
+                .returns(2_u32),
+            DiamondMock::Root::root
+                .next_call(matching!())
+                .returns(3_u32),
+        ));
+
+        assert_eq!(1, u.root());
+        assert_eq!(2, u.root());
+        assert_eq!(3, u.root());
+    }
+}
+
+mod shadowed_std_name {
+    use super::*;
+
+    #[unimock::export]
+    pub trait Debug {
+        fn describe(&self) -> String;
+    }
+
+    #[unimock(api=ReportMock, with_supertraits)]
+    pub trait Report: self::Debug {}
+
+    #[test]
+    fn own_trait_named_like_a_std_trait_is_mocked_by_path() {
+        let u = Unimock::new(
+            ReportMock::Debug::describe
+                .next_call(matching!())
+                .returns("report".to_string()),
+        );
+
+        assert_eq!("report", Debug::describe(&u));
+    }
+}
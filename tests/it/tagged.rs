@@ -0,0 +1,79 @@
+use unimock::*;
+
+#[unimock(api=StorageMock)]
+trait Storage {
+    fn get(&self, key: &str) -> Option<i32>;
+}
+
+fn read(primary: &impl Storage, replica: &impl Storage, key: &str) -> Option<i32> {
+    primary.get(key).or_else(|| replica.get(key))
+}
+
+#[test]
+fn tagged_instances_match_tagged_patterns() {
+    let primary = Unimock::new((
+        StorageMock::get.some_call(matching!("a")).returns(None),
+        StorageMock::get
+            .tag("replica")
+            .some_call(matching!("a"))
+            .returns(Some(1)),
+    ));
+    let replica = primary.tagged("replica");
+
+    assert_eq!(Some(1), read(&primary, &replica, "a"));
+}
+
+#[test]
+fn tagged_stub() {
+    let u = Unimock::new((
+        StorageMock::get.each_call(matching!(_)).returns(Some(1)),
+        StorageMock::get.tag("replica").stub(|each| {
+            each.call(matching!("a")).returns(Some(2));
+            each.call(matching!(_)).returns(None);
+        }),
+    ));
+    let replica = u.tagged("replica");
+
+    assert_eq!(Some(1), u.get("a"));
+    assert_eq!(Some(2), replica.get("a"));
+    assert_eq!(None, replica.get("b"));
+    assert_eq!(Some(2), replica.clone().get("a"));
+}
+
+#[test]
+fn tagged_in_order() {
+    let primary = Unimock::new((
+        StorageMock::get.next_call(matching!("a")).returns(None),
+        StorageMock::get
+            .tag("replica")
+            .next_call(matching!("a"))
+            .returns(Some(1)),
+    ));
+    let replica = primary.tagged("replica");
+
+    assert_eq!(Some(1), read(&primary, &replica, "a"));
+}
+
+#[test]
+#[should_panic(expected = "Storage::get(\"a\") on \"replica\": No matching call patterns.")]
+fn tagged_instance_does_not_match_untagged_pattern() {
+    let u =
+        Unimock::new(StorageMock::get.each_call(matching!(_)).returns(None)).no_verify_in_drop();
+
+    u.tagged("replica").get("a");
+}
+
+#[test]
+#[should_panic(
+    expected = "Storage::get(\"a\"): Method invoked in the correct order (1), but Storage::get(\"a\") at tests/it/tagged.rs:74 expected a call on the instance tagged \"replica\"."
+)]
+fn tag_mismatch_in_order() {
+    let u = Unimock::new(
+        StorageMock::get
+            .tag("replica")
+            .next_call(matching!("a"))
+            .returns(None),
+    );
+
+    u.get("a");
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use unimock::*;
+
+#[unimock(api=ActorMock)]
+trait Actor {
+    fn handle(&self, message: i32);
+}
+
+#[test]
+fn verify_after_instance_moved_to_other_thread() {
+    let mut u = Unimock::new(ActorMock::handle.next_call(matching!(1)).returns(()));
+    let verifier = u.verifier();
+
+    std::thread::spawn(move || {
+        let clone = u.clone();
+        clone.handle(1);
+    });
+
+    verifier.wait_and_verify(Duration::from_secs(10));
+}
+
+#[test]
+#[should_panic(
+    expected = "Actor::handle: Expected Actor::handle(1) at tests/it/verifier.rs:28 to match exactly 1 call, but it actually matched no calls."
+)]
+fn verifier_reports_missing_calls() {
+    let mut u = Unimock::new(ActorMock::handle.next_call(matching!(1)).returns(()));
+    let verifier = u.verifier();
+
+    std::thread::spawn(move || drop(u));
+
+    verifier.wait_and_verify(Duration::from_secs(10));
+}
+
+#[test]
+#[should_panic(expected = "Actor::handle(2) in thread 'actor': No matching call patterns.")]
+fn verifier_forwards_errors_from_other_threads() {
+    let mut u = Unimock::new(ActorMock::handle.each_call(matching!(1)).returns(()));
+    let verifier = u.verifier();
+
+    let _ = std::thread::Builder::new()
+        .name("actor".to_string())
+        .spawn(move || u.handle(2))
+        .unwrap()
+        .join();
+
+    verifier.wait_and_verify(Duration::from_secs(10));
+}
+
+#[test]
+#[should_panic(
+    expected = "Unimock verifier timed out after 10ms, while 1 instance(s) were still alive."
+)]
+fn verifier_times_out_while_instances_are_alive() {
+    let mut u = Unimock::new(());
+    let verifier = u.verifier();
+
+    verifier.wait_and_verify(Duration::from_millis(10));
+    drop(u);
+}
+
+#[test]
+#[should_panic(
+    expected = "Actor::handle: Expected Actor::handle(1) at tests/it/verifier.rs:68 to match exactly 1 call, but it actually matched no calls."
+)]
+fn dropped_verifier_verifies() {
+    let mut u = Unimock::new(ActorMock::handle.next_call(matching!(1)).returns(()));
+    let verifier = u.verifier();
+
+    drop(u);
+    drop(verifier);
+}
+
+#[test]
+#[should_panic(
+    expected = "Unimock verifier dropped while 1 instance(s) were still alive. Use wait_and_verify to wait for them."
+)]
+fn dropped_verifier_panics_while_instances_are_alive() {
+    let mut u = Unimock::new(());
+    let verifier = u.verifier();
+
+    drop(verifier);
+    drop(u);
+}
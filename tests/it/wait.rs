@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use unimock::*;
+
+use crate::AsyncTest;
+
+#[unimock(api=WorkerMock)]
+trait Worker {
+    fn work(&self, job: i32);
+    fn rest(&self);
+}
+
+#[test]
+fn wait_for_calls_on_other_thread() {
+    let u = Unimock::new(WorkerMock::work.each_call(matching!(_)).returns(()));
+
+    let clone = u.clone();
+    let handle = std::thread::spawn(move || {
+        for job in 0..3 {
+            clone.work(job);
+        }
+    });
+
+    u.wait_for::<WorkerMock::work>(3, Duration::from_secs(10));
+    handle.join().unwrap();
+}
+
+#[test]
+#[should_panic(
+    expected = "Worker::work: Timed out after 10ms waiting for it to match 2 calls, but it matched 1 call."
+)]
+fn wait_for_times_out() {
+    let u = Unimock::new(WorkerMock::work.each_call(matching!(_)).returns(()));
+
+    u.work(1);
+    u.wait_for::<WorkerMock::work>(2, Duration::from_millis(10));
+}
+
+#[test]
+#[should_panic(
+    expected = "Worker::rest: Cannot wait for calls, because there are no call patterns that count them."
+)]
+fn wait_for_unmocked_fn() {
+    let u = Unimock::new(());
+
+    u.wait_for::<WorkerMock::rest>(1, Duration::from_secs(10));
+}
+
+#[test]
+fn until_called_in_other_task() {
+    async {
+        let u = Unimock::new(WorkerMock::work.each_call(matching!(_)).returns(()));
+
+        let clone = u.clone();
+        let task = tokio_1::spawn(async move {
+            tokio_1::task::yield_now().await;
+            clone.work(1);
+            clone.work(2);
+        });
+
+        tokio_1::time::timeout(
+            Duration::from_secs(10),
+            u.until_called::<WorkerMock::work>(2),
+        )
+        .await
+        .unwrap();
+        task.await.unwrap();
+    }
+    .test();
+}
+
+#[test]
+fn until_called_registers_one_waker_per_future() {
+    use std::future::Future;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let u = Unimock::new(WorkerMock::work.each_call(matching!(_)).returns(()));
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut until_called = u.until_called::<WorkerMock::work>(1);
+    for _ in 0..3 {
+        assert_eq!(
+            Poll::Pending,
+            std::pin::Pin::new(&mut until_called).poll(&mut cx)
+        );
+    }
+
+    u.work(1);
+
+    assert_eq!(1, counter.0.load(Ordering::SeqCst));
+    assert_eq!(
+        Poll::Ready(()),
+        std::pin::Pin::new(&mut until_called).poll(&mut cx)
+    );
+}
@@ -0,0 +1,482 @@
+//! Exporting trait definitions, so that downstream crates can mock them by path.
+
+use proc_macro2::{Group, Punct, Spacing, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+
+use crate::unimock;
+
+/// Input to the `mock_trait!` macro: `path::to::Trait, <unimock attribute arguments>`.
+pub struct MockTraitInput {
+    trait_path: syn::Path,
+    attr_tokens: TokenStream,
+}
+
+impl syn::parse::Parse for MockTraitInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let trait_path: syn::Path = input.parse()?;
+        let attr_tokens = if input.is_empty() {
+            TokenStream::new()
+        } else {
+            let _: syn::token::Comma = input.parse()?;
+            input.parse()?
+        };
+
+        Ok(Self {
+            trait_path,
+            attr_tokens,
+        })
+    }
+}
+
+/// Input to the exported trait macro's callback: `[path::to::Trait] [<attribute arguments>] trait Trait { .. }`.
+pub struct ExportedTraitInput {
+    trait_path: syn::Path,
+    attr_tokens: TokenStream,
+    item_trait: syn::ItemTrait,
+}
+
+impl syn::parse::Parse for ExportedTraitInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let path_content;
+        let _ = syn::bracketed!(path_content in input);
+        let attr_content;
+        let _ = syn::bracketed!(attr_content in input);
+
+        Ok(Self {
+            trait_path: path_content.parse()?,
+            attr_tokens: attr_content.parse()?,
+            item_trait: input.parse()?,
+        })
+    }
+}
+
+/// The name of the `macro_rules!` macro that `#[unimock::export]` defines for a trait.
+///
+/// `#[macro_export]` places it at the root of the exporting crate.
+fn export_macro_ident(trait_ident: &syn::Ident) -> syn::Ident {
+    format_ident!("__unimock_export_{}", trait_ident)
+}
+
+/// The path to the exported macro of the trait at `trait_path`, which lives at the root of the crate that defines the trait.
+///
+/// Macros exported from the current crate are only in scope as textual `macro_rules!` items.
+fn export_macro_path(trait_path: &syn::Path, macro_ident: &syn::Ident) -> TokenStream {
+    let first_ident = &trait_path.segments[0].ident;
+    let is_local = trait_path.leading_colon.is_none()
+        && (trait_path.segments.len() == 1
+            || first_ident == "crate"
+            || first_ident == "self"
+            || first_ident == "super");
+
+    if is_local {
+        quote! { #macro_ident }
+    } else {
+        let leading_colon = &trait_path.leading_colon;
+        quote! { #leading_colon #first_ident::#macro_ident }
+    }
+}
+
+pub fn export(item_trait: syn::ItemTrait) -> syn::Result<TokenStream> {
+    let macro_ident = export_macro_ident(&item_trait.ident);
+
+    let mut exported_trait = item_trait.clone();
+    // Unimock attributes belong to the exporting crate's own mock
+    exported_trait.attrs.retain(|attribute| {
+        !attribute
+            .path()
+            .segments
+            .iter()
+            .any(|segment| segment.ident == "unimock")
+    });
+    // Default bodies are only used for knowing that a default implementation exists
+    for item in &mut exported_trait.items {
+        if let syn::TraitItem::Fn(trait_item_fn) = item {
+            if let Some(block) = &mut trait_item_fn.default {
+                block.stmts.clear();
+            }
+        }
+    }
+    let exported_tokens = dollar_crate(exported_trait.into_token_stream());
+
+    Ok(quote! {
+        #item_trait
+
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #macro_ident {
+            ([$($callback:tt)*] $($args:tt)*) => {
+                $($callback)*! { $($args)* #exported_tokens }
+            };
+        }
+    })
+}
+
+pub fn mock_trait(input: MockTraitInput) -> syn::Result<TokenStream> {
+    let MockTraitInput {
+        trait_path,
+        attr_tokens,
+    } = input;
+
+    let attr: unimock::Attr = syn::parse2(attr_tokens.clone())?;
+    if let Some(mirror) = &attr.mirror {
+        return Err(syn::Error::new_spanned(
+            mirror,
+            "The mirror is given by the trait path",
+        ));
+    }
+    let prefix = &attr.prefix;
+
+    let trait_ident = match trait_path.segments.last() {
+        Some(last_segment) => &last_segment.ident,
+        None => {
+            return Err(syn::Error::new_spanned(
+                &trait_path,
+                "Expected a trait path",
+            ))
+        }
+    };
+    let macro_path = export_macro_path(&trait_path, &export_macro_ident(trait_ident));
+
+    Ok(quote! {
+        #macro_path! {
+            [#prefix::__mock_exported_trait] [#trait_path] [#attr_tokens]
+        }
+    })
+}
+
+pub fn mock_exported_trait(input: ExportedTraitInput) -> syn::Result<TokenStream> {
+    let ExportedTraitInput {
+        trait_path,
+        attr_tokens,
+        item_trait,
+    } = input;
+
+    let mut attr: unimock::Attr = syn::parse2(attr_tokens)?;
+    attr.mirror = Some(trait_path);
+    // The trait may be defined in a third crate, where implementing it for `Unimock` would
+    // break the orphan rules. The newtype is local to the crate that mocks the trait.
+    attr.standalone_only = attr.standalone.is_some();
+
+    // The trait tokens come out of a `macro_rules!` expansion, so identifiers like `self` would
+    // otherwise not be visible to the code generated at the call site.
+    let item_trait = syn::parse2(call_site_hygiene(item_trait.into_token_stream()))?;
+
+    unimock::generate(attr, item_trait)
+}
+
+/// Supertraits that `Unimock` already implements, which need no mock.
+const IMPLEMENTED_SUPERTRAITS: &[&str] = &[
+    "Send",
+    "Sync",
+    "Sized",
+    "Unpin",
+    "Clone",
+    "UnwindSafe",
+    "RefUnwindSafe",
+];
+
+/// Standard library supertraits, which have no exported definition to mock.
+const STD_SUPERTRAITS: &[&str] = &[
+    "Copy",
+    "Debug",
+    "Display",
+    "Default",
+    "PartialEq",
+    "Eq",
+    "PartialOrd",
+    "Ord",
+    "Hash",
+    "Any",
+    "Error",
+    "Iterator",
+    "Future",
+    "AsRef",
+    "AsMut",
+    "From",
+    "Into",
+    "Deref",
+    "DerefMut",
+    "Drop",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+];
+
+/// Input to the `__mock_supertrait` callback, which mocks one supertrait and continues with the next one:
+/// `[path::to::Trait] [<mocked trait idents>] [<pending trait paths>] [<attribute arguments>] trait Trait { .. }`.
+///
+/// The attribute arguments are shared by all supertraits, the `api` module of each one is named after it.
+pub struct SupertraitInput {
+    trait_path: syn::Path,
+    visited: Vec<syn::Ident>,
+    pending: Vec<syn::Path>,
+    attr_tokens: TokenStream,
+    item_trait: TokenStream,
+}
+
+impl syn::parse::Parse for SupertraitInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let path_content;
+        let _ = syn::bracketed!(path_content in input);
+        let visited_content;
+        let _ = syn::bracketed!(visited_content in input);
+        let pending_content;
+        let _ = syn::bracketed!(pending_content in input);
+        let attr_content;
+        let _ = syn::bracketed!(attr_content in input);
+
+        Ok(Self {
+            trait_path: path_content.parse()?,
+            visited: visited_content
+                .parse_terminated(syn::Ident::parse_any, syn::token::Comma)?
+                .into_iter()
+                .collect(),
+            pending: pending_content
+                .parse_terminated(syn::Path::parse, syn::token::Comma)?
+                .into_iter()
+                .collect(),
+            attr_tokens: attr_content.parse()?,
+            item_trait: input.parse()?,
+        })
+    }
+}
+
+/// Mock the supertraits of a trait, by invoking the macros that `#[unimock::export]` defined for them.
+///
+/// The output is placed inside the subtrait's mock module.
+/// Supertraits are mocked one at a time, each in a module named after it,
+/// and every supertrait in the tree is mocked once, even when several traits share it.
+pub fn mock_supertraits(
+    item_trait: &syn::ItemTrait,
+    attr: &unimock::Attr,
+) -> syn::Result<TokenStream> {
+    let mut visited = vec![];
+    let mut pending = vec![];
+    for path in mocked_supertraits(item_trait)? {
+        push_unvisited(path, &mut visited, &mut pending);
+    }
+
+    let Some(next) =
+        next_supertrait_invocation(&attr.prefix, &visited, pending, &common_attr_tokens(attr))
+    else {
+        return Ok(TokenStream::new());
+    };
+
+    Ok(quote! {
+        #[allow(unused_imports)]
+        use super::*;
+
+        #next
+    })
+}
+
+/// Mock one supertrait, then continue with the next pending one.
+pub fn mock_supertrait(input: SupertraitInput) -> syn::Result<TokenStream> {
+    let SupertraitInput {
+        trait_path,
+        mut visited,
+        mut pending,
+        attr_tokens,
+        item_trait,
+    } = input;
+
+    let item_trait: syn::ItemTrait = syn::parse2(call_site_hygiene(item_trait))?;
+    for path in mocked_supertraits(&item_trait)? {
+        push_unvisited(path, &mut visited, &mut pending);
+    }
+
+    let trait_ident = match trait_path.segments.last() {
+        Some(last_segment) => &last_segment.ident,
+        None => {
+            return Err(syn::Error::new_spanned(
+                &trait_path,
+                "Expected a trait path",
+            ))
+        }
+    };
+    let mut attr: unimock::Attr = syn::parse2(quote! { api = #trait_ident, #attr_tokens })?;
+    let next = next_supertrait_invocation(&attr.prefix, &visited, pending, &attr_tokens);
+    attr.mirror = Some(trait_path);
+    attr.nested_api = true;
+    let mock = unimock::generate(attr, item_trait)?;
+
+    Ok(quote! {
+        #mock
+        #next
+    })
+}
+
+/// Re-export the mock modules of the direct supertraits of a nested supertrait,
+/// which are defined next to it in the subtrait's mock module.
+pub fn reexport_supertraits(item_trait: &syn::ItemTrait) -> syn::Result<TokenStream> {
+    let idents = mocked_supertraits(item_trait)?
+        .into_iter()
+        .filter_map(|path| path.segments.last().map(|segment| segment.ident.clone()));
+
+    Ok(quote! {
+        #(pub use super::#idents;)*
+    })
+}
+
+/// The attribute arguments of every supertrait mock, inherited from the subtrait.
+fn common_attr_tokens(attr: &unimock::Attr) -> TokenStream {
+    let prefix = &attr.prefix;
+    let api_style = attr.api_style.as_ref().map(|api_style| {
+        let lit = &api_style.lit;
+        quote! { , api_style = #lit }
+    });
+
+    quote! { prefix = #prefix #api_style }
+}
+
+fn push_unvisited(path: syn::Path, visited: &mut Vec<syn::Ident>, pending: &mut Vec<syn::Path>) {
+    let Some(last_segment) = path.segments.last() else {
+        return;
+    };
+    // Mock modules are named after the trait, so the name identifies the mock
+    if visited.contains(&last_segment.ident) {
+        return;
+    }
+    visited.push(last_segment.ident.clone());
+    pending.push(path);
+}
+
+/// Invoke the exported macro of the first pending supertrait, with [mock_supertrait] as the callback.
+fn next_supertrait_invocation(
+    prefix: &syn::Path,
+    visited: &[syn::Ident],
+    mut pending: Vec<syn::Path>,
+    attr_tokens: &TokenStream,
+) -> Option<TokenStream> {
+    if pending.is_empty() {
+        return None;
+    }
+    let path = pending.remove(0);
+    let trait_ident = &path.segments.last()?.ident;
+
+    let mut macro_ident = export_macro_ident(trait_ident);
+    macro_ident.set_span(trait_ident.span());
+    let macro_path = export_macro_path(&path, &macro_ident);
+    let first_ident = &path.segments[0].ident;
+
+    // The invocation is expanded inside the subtrait's mock module
+    let mut trait_path = syn::Path {
+        leading_colon: path.leading_colon,
+        segments: path
+            .segments
+            .iter()
+            .map(|segment| syn::PathSegment::from(segment.ident.clone()))
+            .collect(),
+    };
+    if trait_path.leading_colon.is_none() && first_ident != "crate" && first_ident != "$crate" {
+        if first_ident == "self" {
+            trait_path.segments[0] = syn::parse_quote! { super };
+        } else {
+            trait_path.segments.insert(0, syn::parse_quote! { super });
+        }
+    }
+
+    Some(quote! {
+        #macro_path! {
+            [#prefix::__mock_supertrait] [#trait_path] [#(#visited),*] [#(#pending),*] [#attr_tokens]
+        }
+    })
+}
+
+/// The paths of the supertraits that need a mock, i.e. the ones that are not implemented by `Unimock` already.
+fn mocked_supertraits(item_trait: &syn::ItemTrait) -> syn::Result<Vec<syn::Path>> {
+    let mut paths = vec![];
+
+    for bound in &item_trait.supertraits {
+        let syn::TypeParamBound::Trait(trait_bound) = bound else {
+            continue;
+        };
+        if !matches!(trait_bound.modifier, syn::TraitBoundModifier::None) {
+            continue;
+        }
+        let path = &trait_bound.path;
+        let Some(last_segment) = path.segments.last() else {
+            continue;
+        };
+        let ident_string = last_segment.ident.to_string();
+
+        // A bare name of a standard trait is assumed to refer to it, while a path into the standard library always does.
+        // A trait of the user's own that shares its name with a standard trait can be written as e.g. `self::Debug`.
+        let is_std = if path.segments.len() == 1 {
+            IMPLEMENTED_SUPERTRAITS.contains(&ident_string.as_str())
+                || STD_SUPERTRAITS.contains(&ident_string.as_str())
+        } else {
+            let first_ident = &path.segments[0].ident;
+            first_ident == "std" || first_ident == "core" || first_ident == "alloc"
+        };
+        if is_std {
+            if IMPLEMENTED_SUPERTRAITS.contains(&ident_string.as_str()) {
+                continue;
+            }
+            return Err(syn::Error::new_spanned(
+                trait_bound,
+                format!("The supertrait `{ident_string}` is not implemented by Unimock and cannot be mocked. If it is a trait of your own, write it as a path, e.g. `self::{ident_string}`, and export it with `#[unimock::export]`."),
+            ));
+        }
+        if trait_bound.lifetimes.is_some()
+            || matches!(last_segment.arguments, syn::PathArguments::Parenthesized(_))
+        {
+            return Err(syn::Error::new_spanned(
+                trait_bound,
+                "Supertraits with higher-ranked or parenthesized bounds cannot be mocked",
+            ));
+        }
+
+        paths.push(path.clone());
+    }
+
+    Ok(paths)
+}
+
+/// Resolve all identifiers at the call site, except for `$crate`, which must keep pointing into the exporting crate.
+fn call_site_hygiene(tokens: TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "$crate" => TokenTree::Ident(ident),
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), call_site_hygiene(group.stream()));
+                new_group.set_span(group.span().resolved_at(Span::call_site()));
+                TokenTree::Group(new_group)
+            }
+            mut token => {
+                token.set_span(token.span().resolved_at(Span::call_site()));
+                token
+            }
+        })
+        .collect()
+}
+
+/// Rewrite crate-relative paths (`crate::Foo`) to `$crate::Foo`,
+/// so that they resolve to the exporting crate when expanded in another crate.
+fn dollar_crate(tokens: TokenStream) -> TokenStream {
+    let mut output = TokenStream::new();
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "crate" => {
+                if matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == ':')
+                {
+                    output.extend([TokenTree::Punct(Punct::new('$', Spacing::Alone))]);
+                }
+                output.extend([TokenTree::Ident(ident)]);
+            }
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(group.delimiter(), dollar_crate(group.stream()));
+                new_group.set_span(group.span());
+                output.extend([TokenTree::Group(new_group)]);
+            }
+            token => output.extend([token]),
+        }
+    }
+
+    output
+}
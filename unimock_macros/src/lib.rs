@@ -3,6 +3,7 @@
 #![forbid(unsafe_code)]
 
 mod doc;
+mod export;
 mod matching;
 mod unimock;
 
@@ -14,11 +15,20 @@ pub fn unimock(
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
     let attrs = syn::parse_macro_input!(attr as unimock::Attr);
-    let item_trait = syn::parse_macro_input!(input as syn::ItemTrait);
+    let item = syn::parse_macro_input!(input as syn::Item);
 
     let debug = attrs.debug;
 
-    let output = match unimock::generate(attrs, item_trait) {
+    let result = match item {
+        syn::Item::Trait(item_trait) => unimock::generate(attrs, item_trait),
+        syn::Item::Impl(item_impl) => unimock::generate_inherent(attrs, item_impl),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "Expected a trait or an inherent impl block",
+        )),
+    };
+
+    let output = match result {
         Ok(stream) => stream,
         Err(err) => err.to_compile_error(),
     };
@@ -30,6 +40,71 @@ pub fn unimock(
     proc_macro::TokenStream::from(output)
 }
 
+#[proc_macro_attribute]
+pub fn export(
+    attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro2::TokenStream::from(attr)
+                .into_iter()
+                .next()
+                .map(|token| token.span())
+                .unwrap_or_else(proc_macro2::Span::call_site),
+            "Unexpected arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let item_trait = syn::parse_macro_input!(input as syn::ItemTrait);
+
+    let output = match export::export(item_trait) {
+        Ok(stream) => stream,
+        Err(err) => err.to_compile_error(),
+    };
+
+    proc_macro::TokenStream::from(output)
+}
+
+#[proc_macro]
+pub fn mock_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as export::MockTraitInput);
+
+    let output = match export::mock_trait(input) {
+        Ok(stream) => stream,
+        Err(err) => err.to_compile_error(),
+    };
+
+    proc_macro::TokenStream::from(output)
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __mock_exported_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as export::ExportedTraitInput);
+
+    let output = match export::mock_exported_trait(input) {
+        Ok(stream) => stream,
+        Err(err) => err.to_compile_error(),
+    };
+
+    proc_macro::TokenStream::from(output)
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __mock_supertrait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as export::SupertraitInput);
+
+    let output = match export::mock_supertrait(input) {
+        Ok(stream) => stream,
+        Err(err) => err.to_compile_error(),
+    };
+
+    proc_macro::TokenStream::from(output)
+}
+
 #[proc_macro]
 pub fn matching(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as matching::MatchingInput);
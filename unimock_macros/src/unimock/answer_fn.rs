@@ -6,12 +6,35 @@ use crate::unimock::util::{
     guess_is_pin, register_lifetimes_and_substitute_missing, rename_lifetimes, self_type_to_unimock,
 };
 
-use super::{method::MockMethod, trait_info::TraitInfo, Attr};
+use super::{
+    method::{MockMethod, Receiver},
+    trait_info::TraitInfo,
+    Attr,
+};
 
 pub fn make_answer_fn(
     method: &MockMethod,
     trait_info: &TraitInfo,
     attr: &Attr,
+) -> proc_macro2::TokenStream {
+    make_answer_fn_with_output(method, trait_info, attr, None)
+}
+
+/// Make an answer fn type which returns the given owned type instead of the method's output type.
+pub fn make_owned_answer_fn(
+    method: &MockMethod,
+    trait_info: &TraitInfo,
+    attr: &Attr,
+    owned_ty: &syn::Type,
+) -> proc_macro2::TokenStream {
+    make_answer_fn_with_output(method, trait_info, attr, Some(owned_ty))
+}
+
+fn make_answer_fn_with_output(
+    method: &MockMethod,
+    trait_info: &TraitInfo,
+    attr: &Attr,
+    owned_ty: Option<&syn::Type>,
 ) -> proc_macro2::TokenStream {
     let prefix = &attr.prefix;
 
@@ -25,6 +48,12 @@ pub fn make_answer_fn(
 
     let mut args: syn::punctuated::Punctuated<syn::Type, syn::token::Comma> = Default::default();
 
+    // Answers to associated functions get the current instance
+    if matches!(method.receiver(), Receiver::Static { .. }) {
+        hrtbs.insert(self_lifetime.clone());
+        args.push(syn::parse_quote! { & #self_lifetime #prefix::Unimock });
+    }
+
     for fn_arg in &method.adapted_sig.inputs {
         match fn_arg {
             syn::FnArg::Receiver(syn::Receiver {
@@ -98,7 +127,9 @@ pub fn make_answer_fn(
         }
     }
 
-    let arrow_output = if let Some(mut ty) = method.output_structure.output_type_stripped() {
+    let arrow_output = if let Some(owned_ty) = owned_ty {
+        Some(quote! { -> #owned_ty })
+    } else if let Some(mut ty) = method.output_structure.output_type_stripped() {
         rename_lifetimes(&mut ty, &mut |lifetime| match lifetime {
             Some(lifetime) => {
                 if hrtbs.contains(lifetime) {
@@ -12,8 +12,24 @@ pub struct Attr {
     pub associated_consts: HashMap<String, syn::TraitItemConst>,
     unmocks: Option<WithSpan<Vec<Unmock>>>,
     pub mirror: Option<syn::Path>,
+    /// Name of a generated newtype that implements only this trait
+    pub standalone: Option<syn::Ident>,
+    /// Only the standalone newtype implements the trait, as `Unimock` may be foreign to it
+    pub standalone_only: bool,
+    /// Name used for the trait in messages, when it differs from the trait ident
+    pub display_ident: Option<syn::Ident>,
     pub input_lifetime: syn::Lifetime,
     pub debug: bool,
+    /// Formatter for inputs that do not implement `Debug`
+    pub debug_inputs: Option<syn::Path>,
+    /// Formatters for specific method parameters, by method ident and parameter index
+    pub arg_debug_inputs: HashMap<(String, usize), syn::Path>,
+    /// Also mock the supertraits, with mock APIs nested in the mock module
+    pub with_supertraits: Option<proc_macro2::Span>,
+    /// The mock module is nested in the mock module of a subtrait. Not an argument, only set for supertrait mocks
+    pub nested_api: bool,
+    /// Naming style of the MockFn structs in the mock module
+    pub api_style: Option<ApiStyle>,
 }
 
 pub struct WithSpan<T>(pub T, pub proc_macro2::Span);
@@ -29,6 +45,40 @@ impl Attr {
         })
     }
 
+    /// Remove `#[unimock(debug_inputs = ..)]` attributes from the method parameters of the trait,
+    /// and remember the formatters.
+    pub fn take_arg_attrs(&mut self, item_trait: &mut syn::ItemTrait) -> syn::Result<()> {
+        for item in &mut item_trait.items {
+            let syn::TraitItem::Fn(trait_item_fn) = item else {
+                continue;
+            };
+            let method_ident = trait_item_fn.sig.ident.to_string();
+
+            for (index, fn_arg) in trait_item_fn.sig.inputs.iter_mut().enumerate() {
+                let syn::FnArg::Typed(pat_type) = fn_arg else {
+                    continue;
+                };
+                let mut result = Ok(());
+                pat_type.attrs.retain(|attribute| {
+                    if !attribute.path().is_ident("unimock") {
+                        return true;
+                    }
+                    match attribute.parse_args::<ArgAttr>() {
+                        Ok(arg_attr) => {
+                            self.arg_debug_inputs
+                                .insert((method_ident.clone(), index), arg_attr.debug_inputs);
+                        }
+                        Err(err) => result = Err(err),
+                    }
+                    false
+                });
+                result?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn validate(&self, trait_info: &TraitInfo) -> syn::Result<()> {
         match &self.unmocks {
             Some(unmocked) if unmocked.0.len() != trait_info.methods.len() => {
@@ -40,6 +90,26 @@ impl Attr {
             _ => {}
         }
 
+        match (&self.api_style, &self.mock_api) {
+            (Some(_), MockApi::MockMod(_)) | (None, _) => {}
+            (Some(api_style), _) => {
+                return Err(syn::Error::new_spanned(
+                    &api_style.lit,
+                    "api_style requires a mock module, e.g. `api = TraitMock`",
+                ))
+            }
+        }
+
+        match (&self.with_supertraits, &self.mock_api) {
+            (Some(_), MockApi::MockMod(_)) | (None, _) => {}
+            (Some(span), _) => {
+                return Err(syn::Error::new(
+                    *span,
+                    "with_supertraits requires a mock module, e.g. `api = TraitMock`",
+                ))
+            }
+        }
+
         Ok(())
     }
 }
@@ -53,6 +123,10 @@ impl syn::parse::Parse for Attr {
         let mut unmocks = None;
         let mut debug = false;
         let mut mirror = None;
+        let mut standalone = None;
+        let mut debug_inputs = None;
+        let mut with_supertraits = None;
+        let mut api_style = None;
 
         while !input.is_empty() {
             if input.peek(syn::token::Type) {
@@ -111,6 +185,21 @@ impl syn::parse::Parse for Attr {
                         let path: syn::Path = input.parse()?;
                         mirror = Some(path);
                     }
+                    "standalone" => {
+                        let _: syn::token::Eq = input.parse()?;
+                        standalone = Some(input.parse()?);
+                    }
+                    "debug_inputs" => {
+                        let _: syn::token::Eq = input.parse()?;
+                        debug_inputs = Some(input.parse()?);
+                    }
+                    "with_supertraits" => {
+                        with_supertraits = Some(keyword.span());
+                    }
+                    "api_style" => {
+                        let _: syn::token::Eq = input.parse()?;
+                        api_style = Some(input.parse()?);
+                    }
                     _ => return Err(syn::Error::new(keyword.span(), "Unrecognized keyword")),
                 }
 
@@ -129,8 +218,35 @@ impl syn::parse::Parse for Attr {
             associated_consts,
             unmocks,
             mirror,
+            standalone,
+            standalone_only: false,
+            display_ident: None,
             input_lifetime: syn::Lifetime::new("'__i", proc_macro2::Span::call_site()),
             debug,
+            debug_inputs,
+            arg_debug_inputs: HashMap::default(),
+            with_supertraits,
+            nested_api: false,
+            api_style,
+        })
+    }
+}
+
+/// Parsed unimock attribute on a method parameter
+struct ArgAttr {
+    debug_inputs: syn::Path,
+}
+
+impl syn::parse::Parse for ArgAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let keyword: syn::Ident = input.parse()?;
+        if keyword != "debug_inputs" {
+            return Err(syn::Error::new(keyword.span(), "Unrecognized keyword"));
+        }
+        let _: syn::token::Eq = input.parse()?;
+
+        Ok(Self {
+            debug_inputs: input.parse()?,
         })
     }
 }
@@ -145,6 +261,68 @@ pub enum MockApi {
     Flattened(FlattenedMethods),
 }
 
+/// Naming style of the MockFn structs in a mock module, as a template of the struct name.
+///
+/// `{method}` is replaced by the method name, and `{Method}` by the method name in PascalCase.
+pub struct ApiStyle {
+    pub lit: syn::LitStr,
+    template: String,
+}
+
+impl ApiStyle {
+    pub fn mock_fn_ident(&self, method_ident: &syn::Ident) -> syn::Result<syn::Ident> {
+        use syn::ext::IdentExt;
+
+        let method = method_ident.unraw().to_string();
+        let name = self
+            .template
+            .replace("{method}", &method)
+            .replace("{Method}", &pascal_case(&method));
+
+        let mut ident: syn::Ident = syn::parse_str(&name).map_err(|_| {
+            syn::Error::new_spanned(
+                &self.lit,
+                format!("api_style produces `{name}`, which is not a valid identifier"),
+            )
+        })?;
+        ident.set_span(method_ident.span());
+        Ok(ident)
+    }
+}
+
+impl syn::parse::Parse for ApiStyle {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit: syn::LitStr = input.parse()?;
+        let template = match lit.value().as_str() {
+            "snake_case" => "{method}".to_string(),
+            "PascalCase" => "{Method}".to_string(),
+            value if value.contains("{method}") || value.contains("{Method}") => value.to_string(),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &lit,
+                    "Expected \"snake_case\", \"PascalCase\" or a template containing `{method}` or `{Method}`",
+                ))
+            }
+        };
+
+        Ok(Self { lit, template })
+    }
+}
+
+fn pascal_case(snake_case: &str) -> String {
+    snake_case
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
 pub struct FlattenedMethods {
     span: proc_macro2::Span,
     idents: Vec<syn::Ident>,
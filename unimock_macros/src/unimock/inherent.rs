@@ -0,0 +1,214 @@
+use quote::{format_ident, quote};
+
+use super::attr::Attr;
+use super::util::replace_self_ty_with_path;
+
+/// Generate a mock for the methods of an inherent `impl` block.
+///
+/// The method signatures are extracted into a hidden trait which gets mocked as usual,
+/// and a newtype around `Unimock` exposes the mocked methods as inherent methods, so it can substitute the original type.
+/// The mock only exists under `cfg(test)`, where the generated `Mockable<Type>` alias resolves to the newtype instead of the original type.
+pub fn generate(mut attr: Attr, item_impl: syn::ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
+    if let Some((_, trait_path, _)) = &item_impl.trait_ {
+        return Err(syn::Error::new_spanned(
+            trait_path,
+            "Trait impl blocks are not supported. Put the unimock attribute on the trait definition instead.",
+        ));
+    }
+    if !item_impl.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item_impl.generics,
+            "Generic impl blocks are not supported",
+        ));
+    }
+    if attr.mirror.is_some() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`mirror` cannot be used with impl blocks",
+        ));
+    }
+
+    let self_path = match item_impl.self_ty.as_ref() {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => type_path.path.clone(),
+        other => {
+            return Err(syn::Error::new_spanned(
+                other,
+                "Expected a type path, like `Client`",
+            ))
+        }
+    };
+    let self_ident = match self_path.segments.last() {
+        Some(last_segment) => last_segment.ident.clone(),
+        None => return Err(syn::Error::new_spanned(&self_path, "Expected a type path")),
+    };
+
+    let methods: Vec<&syn::ImplItemFn> = item_impl
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::ImplItem::Fn(impl_item_fn) => Some(impl_item_fn),
+            _ => None,
+        })
+        .collect();
+    if methods.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item_impl.self_ty,
+            "The impl block contains no methods",
+        ));
+    }
+
+    let vis = most_visible(methods.iter().map(|method| &method.vis));
+    let prefix = attr.prefix.clone();
+    let trait_ident = format_ident!("__Unimock{}", self_ident);
+    let newtype_ident = format_ident!("Unimock{}", self_ident);
+    let alias_ident = format_ident!("Mockable{}", self_ident);
+
+    // `Self` is the substitute, so that e.g. constructors of the newtype return the newtype
+    let trait_sigs: Vec<syn::Signature> = methods
+        .iter()
+        .map(|method| trait_signature(&method.sig, &newtype_ident.clone().into()))
+        .collect();
+
+    let newtype_methods = methods.iter().zip(&trait_sigs).map(|(method, sig)| {
+        let attrs = method
+            .attrs
+            .iter()
+            .filter(|attribute| attribute.path().is_ident("doc"));
+        let method_vis = &method.vis;
+        let method_ident = &sig.ident;
+        let args = sig.inputs.iter().map(|input| match input {
+            syn::FnArg::Receiver(syn::Receiver {
+                reference: None, ..
+            }) => quote! { self.0 },
+            syn::FnArg::Receiver(syn::Receiver {
+                reference: Some(_),
+                mutability: Some(_),
+                ..
+            }) => quote! { &mut self.0 },
+            syn::FnArg::Receiver(_) => quote! { &self.0 },
+            syn::FnArg::Typed(pat_type) => {
+                let pat = &pat_type.pat;
+                quote! { #pat }
+            }
+        });
+        let dot_await = sig.asyncness.map(|_| quote! { .await });
+
+        quote! {
+            #(#attrs)*
+            #method_vis #sig {
+                <#prefix::Unimock as #trait_ident>::#method_ident(#(#args),*) #dot_await
+            }
+        }
+    });
+
+    let item_trait: syn::ItemTrait = syn::parse_quote! {
+        #[doc(hidden)]
+        #vis trait #trait_ident {
+            #(#trait_sigs;)*
+        }
+    };
+
+    // Errors should mention the original type instead of the hidden trait
+    attr.display_ident = Some(self_ident.clone());
+    let mut mock: syn::File = syn::parse2(super::generate(attr, item_trait)?)?;
+    for item in &mut mock.items {
+        if let Some(attrs) = item_attrs_mut(item) {
+            attrs.insert(0, syn::parse_quote! { #[cfg(test)] });
+        }
+    }
+
+    let newtype_doc = format!(
+        " A `Unimock`-backed substitute for [`{self_ident}`], with the same mockable methods."
+    );
+    let alias_doc = format!(
+        " [`{self_ident}`], except under `cfg(test)`, where it is its mockable substitute `{newtype_ident}`."
+    );
+
+    Ok(quote! {
+        #item_impl
+
+        #mock
+
+        #[doc = #alias_doc]
+        #[cfg(not(test))]
+        #vis type #alias_ident = #self_path;
+
+        #[doc = #alias_doc]
+        #[cfg(test)]
+        #vis type #alias_ident = #newtype_ident;
+
+        #[doc = #newtype_doc]
+        #[cfg(test)]
+        #vis struct #newtype_ident(pub #prefix::Unimock);
+
+        #[cfg(test)]
+        impl ::core::convert::From<#prefix::Unimock> for #newtype_ident {
+            fn from(unimock: #prefix::Unimock) -> Self {
+                Self(unimock)
+            }
+        }
+
+        #[cfg(test)]
+        impl #newtype_ident {
+            #(#newtype_methods)*
+        }
+    })
+}
+
+fn item_attrs_mut(item: &mut syn::Item) -> Option<&mut Vec<syn::Attribute>> {
+    match item {
+        syn::Item::Const(item) => Some(&mut item.attrs),
+        syn::Item::Enum(item) => Some(&mut item.attrs),
+        syn::Item::ExternCrate(item) => Some(&mut item.attrs),
+        syn::Item::Fn(item) => Some(&mut item.attrs),
+        syn::Item::ForeignMod(item) => Some(&mut item.attrs),
+        syn::Item::Impl(item) => Some(&mut item.attrs),
+        syn::Item::Macro(item) => Some(&mut item.attrs),
+        syn::Item::Mod(item) => Some(&mut item.attrs),
+        syn::Item::Static(item) => Some(&mut item.attrs),
+        syn::Item::Struct(item) => Some(&mut item.attrs),
+        syn::Item::Trait(item) => Some(&mut item.attrs),
+        syn::Item::TraitAlias(item) => Some(&mut item.attrs),
+        syn::Item::Type(item) => Some(&mut item.attrs),
+        syn::Item::Union(item) => Some(&mut item.attrs),
+        syn::Item::Use(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
+
+/// The method signature as it appears in the hidden trait.
+///
+/// `Self` refers to the substitute type, and argument patterns are replaced by plain identifiers.
+fn trait_signature(sig: &syn::Signature, self_path: &syn::Path) -> syn::Signature {
+    let mut sig = sig.clone();
+
+    for (index, input) in sig.inputs.iter_mut().enumerate() {
+        if let syn::FnArg::Typed(pat_type) = input {
+            let ident = match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+                _ => format_ident!("__a{}", index),
+            };
+            *pat_type.pat = syn::parse_quote!(#ident);
+            *pat_type.ty = replace_self_ty_with_path(*pat_type.ty.clone(), self_path);
+        }
+    }
+
+    if let syn::ReturnType::Type(_, output_ty) = &mut sig.output {
+        **output_ty = replace_self_ty_with_path(*output_ty.clone(), self_path);
+    }
+
+    sig
+}
+
+fn most_visible<'v>(visibilities: impl Iterator<Item = &'v syn::Visibility>) -> syn::Visibility {
+    let rank = |vis: &syn::Visibility| match vis {
+        syn::Visibility::Public(_) => 2,
+        syn::Visibility::Restricted(_) => 1,
+        syn::Visibility::Inherited => 0,
+    };
+
+    visibilities
+        .max_by_key(|vis| rank(vis))
+        .cloned()
+        .unwrap_or(syn::Visibility::Inherited)
+}
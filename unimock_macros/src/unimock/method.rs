@@ -8,7 +8,7 @@ use syn::visit_mut::VisitMut;
 use super::attr::MockApi;
 use super::output;
 use super::util::{
-    contains_lifetime, guess_is_pin, DotAwait, IsGeneric, IsTypeGeneric, RpitFuture,
+    contains_lifetime, guess_is_pin, DotAwait, IsGeneric, IsTypeGeneric, RpitDyn, RpitFuture,
 };
 use super::Attr;
 
@@ -90,7 +90,7 @@ impl<'t> MockMethod<'t> {
         syntax: InputsSyntax,
         tupled: Tupled,
         attr: &'t Attr,
-    ) -> InputsDestructuring {
+    ) -> InputsDestructuring<'_> {
         InputsDestructuring {
             method: self,
             syntax,
@@ -100,6 +100,12 @@ impl<'t> MockMethod<'t> {
     }
 
     pub fn receiver(&self) -> Receiver {
+        if !has_receiver(&self.method.sig) {
+            return Receiver::Static {
+                surrogate_self: syn::Ident::new("__unimock", self.method.sig.ident.span()),
+            };
+        }
+
         match self.method.sig.receiver() {
             Some(syn::Receiver {
                 reference: None,
@@ -137,7 +143,7 @@ impl<'t> MockMethod<'t> {
             .find(|fn_arg| matches!(fn_arg, syn::FnArg::Typed(_)));
 
         let body = if first_param.is_some() {
-            let inputs_try_debug_exprs = self.inputs_try_debug_exprs();
+            let inputs_try_debug_exprs = self.inputs_try_debug_exprs(attr);
             quote! {
                 use #prefix::private::{ProperDebug, NoDebug};
                 #prefix::alloc::Box::new([#(#inputs_try_debug_exprs),*])
@@ -155,17 +161,31 @@ impl<'t> MockMethod<'t> {
         })
     }
 
-    pub fn inputs_try_debug_exprs(&self) -> impl Iterator<Item = proc_macro2::TokenStream> + 't {
+    pub fn inputs_try_debug_exprs(
+        &self,
+        attr: &'t Attr,
+    ) -> impl Iterator<Item = proc_macro2::TokenStream> + 't {
+        let method_ident = self.method.sig.ident.to_string();
         self.method
             .sig
             .inputs
             .iter()
             .enumerate()
-            .filter_map(|(index, fn_arg)| match fn_arg {
+            .filter_map(move |(index, fn_arg)| match fn_arg {
                 syn::FnArg::Receiver(_) => None,
                 syn::FnArg::Typed(pat_type) => match (index, pat_type.pat.as_ref()) {
                     (_, syn::Pat::Ident(pat_ident)) => {
-                        Some(try_debug_expr(pat_ident, &pat_type.ty))
+                        match attr.arg_debug_inputs.get(&(method_ident.clone(), index)) {
+                            Some(debug_fn) => {
+                                let ident = &pat_ident.ident;
+                                Some(quote! { Some(#debug_fn(#ident)) })
+                            }
+                            None => Some(try_debug_expr(
+                                pat_ident,
+                                &pat_type.ty,
+                                attr.debug_inputs.as_ref(),
+                            )),
+                        }
                     }
                     _ => Some(
                         syn::Error::new(pat_type.span(), "Unprocessable argument")
@@ -240,17 +260,17 @@ pub fn extract_methods<'s>(
             match determine_mockable(method) {
                 Mockable::Yes => {}
                 Mockable::Skip => return Ok(None),
-                Mockable::Err(err) => return Err(err),
             };
 
             let mut adapted_sig = method.sig.clone();
-            let adapt_sig_result = adapt_sig(&mut adapted_sig);
+            let adapt_sig_result = adapt_sig(&mut adapted_sig, attr)?;
             let is_type_generic =
                 IsTypeGeneric(is_trait_type_generic.0 || adapt_sig_result.is_type_generic.0);
 
             let output_structure = output::determine_output_structure(
                 &adapted_sig,
                 adapt_sig_result.rpit_future,
+                adapt_sig_result.rpit_dyn,
                 item_trait,
                 attr,
             );
@@ -314,34 +334,27 @@ pub fn extract_methods<'s>(
 enum Mockable {
     Yes,
     Skip,
-    Err(syn::Error),
 }
 
 fn determine_mockable(method: &syn::TraitItemFn) -> Mockable {
-    fn is_receiver(first_fn_arg: Option<&syn::FnArg>) -> bool {
-        match first_fn_arg {
-            None => false,
-            Some(syn::FnArg::Receiver(_)) => true,
-            Some(syn::FnArg::Typed(pat_type)) => match pat_type.pat.as_ref() {
-                syn::Pat::Ident(pat_ident) => pat_ident.ident == "self",
-                // Probably not mockable, but try, then generate compile error later:
-                _ => true,
-            },
-        }
-    }
-
-    let first_fn_arg = method.sig.inputs.first();
-
-    if is_receiver(first_fn_arg) {
+    if has_receiver(&method.sig) || method.default.is_none() {
+        // Methods without a receiver get evaluated by the current Unimock instance
         Mockable::Yes
-    } else if method.default.is_some() {
+    } else {
         // method is provided, skip
         Mockable::Skip
-    } else {
-        Mockable::Err(syn::Error::new(
-            method.sig.ident.span(),
-            "Method has no self receiver and no default body. Mocking will not work.",
-        ))
+    }
+}
+
+fn has_receiver(sig: &syn::Signature) -> bool {
+    match sig.inputs.first() {
+        None => false,
+        Some(syn::FnArg::Receiver(_)) => true,
+        Some(syn::FnArg::Typed(pat_type)) => match pat_type.pat.as_ref() {
+            syn::Pat::Ident(pat_ident) => pat_ident.ident == "self",
+            // Probably not mockable, but try, then generate compile error later:
+            _ => true,
+        },
     }
 }
 
@@ -364,13 +377,20 @@ fn generate_mock_fn_ident(
     } else {
         match &attr.mock_api {
             MockApi::Flattened(flat_mocks) => Ok(flat_mocks.get_mock_ident(method_index)?.clone()),
-            MockApi::MockMod(_) => Ok(method.sig.ident.clone()),
+            MockApi::MockMod(_) => match &attr.api_style {
+                Some(api_style) => api_style.mock_fn_ident(&method.sig.ident),
+                None => Ok(method.sig.ident.clone()),
+            },
             MockApi::Hidden => Ok(quote::format_ident!("UnimockHidden__{}", method.sig.ident)),
         }
     }
 }
 
-fn try_debug_expr(pat_ident: &syn::PatIdent, ty: &syn::Type) -> proc_macro2::TokenStream {
+fn try_debug_expr(
+    pat_ident: &syn::PatIdent,
+    ty: &syn::Type,
+    fallback: Option<&syn::Path>,
+) -> proc_macro2::TokenStream {
     #[derive(Clone, Copy)]
     enum InnerKind {
         Slice,
@@ -403,13 +423,18 @@ fn try_debug_expr(pat_ident: &syn::PatIdent, ty: &syn::Type) -> proc_macro2::Tok
     let mut derefs: Vec<TokenStream> = vec![];
     collect_derefs(ty, &mut derefs);
 
+    let try_debug = match fallback {
+        Some(fallback) => quote! { unimock_try_debug_or(#fallback) },
+        None => quote! { unimock_try_debug() },
+    };
+
     if derefs.is_empty() {
         quote! {
-            #ident.unimock_try_debug()
+            #ident.#try_debug
         }
     } else {
         quote! {
-            (#(#derefs)* #ident).unimock_try_debug()
+            (#(#derefs)* #ident).#try_debug
         }
     }
 }
@@ -486,10 +511,11 @@ struct AdaptSigResult {
     is_type_generic: IsTypeGeneric,
     impl_trait_idents: HashSet<String>,
     rpit_future: Option<RpitFuture>,
+    rpit_dyn: Option<RpitDyn>,
 }
 
 // TODO: Rewrite impl Trait to normal param
-fn adapt_sig(sig: &mut syn::Signature) -> AdaptSigResult {
+fn adapt_sig(sig: &mut syn::Signature, attr: &Attr) -> syn::Result<AdaptSigResult> {
     let mut generics: syn::Generics = Default::default();
     let mut impl_trait_idents: HashSet<String> = HashSet::new();
     std::mem::swap(&mut sig.generics, &mut generics);
@@ -539,6 +565,15 @@ fn adapt_sig(sig: &mut syn::Signature) -> AdaptSigResult {
         }
 
         fn visit_return_type_mut(&mut self, i: &mut syn::ReturnType) {
+            if let syn::ReturnType::Type(_, ty) = i {
+                if let syn::Type::ImplTrait(impl_trait) = ty.as_ref() {
+                    if find_future_bound(impl_trait.bounds.iter()).is_none() {
+                        // handled later, as a boxed trait object
+                        return;
+                    }
+                }
+            }
+
             self.cur_is_return = true;
             syn::visit_mut::visit_return_type_mut(self, i);
             self.cur_is_return = false;
@@ -556,29 +591,56 @@ fn adapt_sig(sig: &mut syn::Signature) -> AdaptSigResult {
 
     let rpit_future = converter.rpit_future;
 
+    // A non-future `impl Trait` in return position gets represented as a boxed trait object
+    let mut rpit_dyn = None;
+    if rpit_future.is_none() {
+        if let syn::ReturnType::Type(_, output_ty) = &mut sig.output {
+            if let syn::Type::ImplTrait(impl_trait) = output_ty.as_ref() {
+                let prefix = &attr.prefix;
+                let dyn_ty = RpitDyn::dyn_type(impl_trait)?;
+                **output_ty = syn::parse_quote! {
+                    #prefix::alloc::Box<#dyn_ty>
+                };
+                rpit_dyn = Some(RpitDyn { dyn_ty });
+            }
+        }
+    }
+
     // write back generics
     std::mem::swap(&mut generics, &mut sig.generics);
 
     let mut is_type_generic = IsTypeGeneric(false);
     for generic_param in &sig.generics.params {
-        if matches!(generic_param, syn::GenericParam::Type(_)) {
+        if matches!(
+            generic_param,
+            syn::GenericParam::Type(_) | syn::GenericParam::Const(_)
+        ) {
             is_type_generic.0 = true;
         }
     }
 
-    AdaptSigResult {
+    Ok(AdaptSigResult {
         is_generic: IsGeneric(!sig.generics.params.is_empty()),
         is_type_generic,
         impl_trait_idents,
         rpit_future,
-    }
+        rpit_dyn,
+    })
 }
 
 pub enum Receiver {
     Owned,
     Ref,
-    MutRef { surrogate_self: syn::Ident },
-    Pin { surrogate_self: syn::Ident },
+    MutRef {
+        surrogate_self: syn::Ident,
+    },
+    Pin {
+        surrogate_self: syn::Ident,
+    },
+    /// No receiver: the current Unimock instance gets bound to the surrogate.
+    Static {
+        surrogate_self: syn::Ident,
+    },
 }
 
 pub struct SelfReference<'a>(pub &'a Receiver);
@@ -596,6 +658,10 @@ impl<'a> quote::ToTokens for SelfReference<'a> {
             Receiver::Pin { surrogate_self } | Receiver::MutRef { surrogate_self } => {
                 surrogate_self.to_tokens(tokens);
             }
+            Receiver::Static { surrogate_self } => {
+                syn::token::And::default().to_tokens(tokens);
+                surrogate_self.to_tokens(tokens);
+            }
         }
     }
 }
@@ -608,7 +674,7 @@ impl<'a> quote::ToTokens for SelfToDelegator<'a> {
             Receiver::Owned | Receiver::Ref => {
                 syn::token::SelfValue::default().to_tokens(tokens);
             }
-            Receiver::MutRef { surrogate_self } => {
+            Receiver::MutRef { surrogate_self } | Receiver::Static { surrogate_self } => {
                 surrogate_self.to_tokens(tokens);
             }
             Receiver::Pin { surrogate_self } => {
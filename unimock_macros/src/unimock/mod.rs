@@ -4,6 +4,7 @@ use syn::parse_quote;
 mod answer_fn;
 mod associated_future;
 mod attr;
+mod inherent;
 mod method;
 mod output;
 mod trait_info;
@@ -13,15 +14,20 @@ use crate::doc::SynDoc;
 use crate::unimock::method::{InputsSyntax, Receiver, SelfReference, SelfToDelegator, Tupled};
 use crate::unimock::util::replace_self_ty_with_path;
 pub use attr::{Attr, MockApi};
+pub use inherent::generate as generate_inherent;
 use trait_info::TraitInfo;
 
 use attr::{UnmockFn, UnmockFnParams};
 
-use self::answer_fn::make_answer_fn;
+use self::answer_fn::{make_answer_fn, make_owned_answer_fn};
 use self::method::{ArgClass, MockMethod};
 use self::util::{iter_generic_type_params, InferImplTrait};
 
-pub fn generate(attr: Attr, item_trait: syn::ItemTrait) -> syn::Result<proc_macro2::TokenStream> {
+pub fn generate(
+    mut attr: Attr,
+    mut item_trait: syn::ItemTrait,
+) -> syn::Result<proc_macro2::TokenStream> {
+    attr.take_arg_attrs(&mut item_trait)?;
     let trait_info = trait_info::TraitInfo::analyze(&item_trait, &attr)?;
     attr.validate(&trait_info)?;
 
@@ -139,13 +145,65 @@ pub fn generate(attr: Attr, item_trait: syn::ItemTrait) -> syn::Result<proc_macr
             let impl_doc_lit_str =
                 syn::LitStr::new(&impl_doc_string, proc_macro2::Span::call_site());
 
-            let vis = &trait_info.input_trait.vis;
+            let vis = if attr.nested_api {
+                syn::Visibility::Public(syn::token::Pub(proc_macro2::Span::call_site()))
+            } else {
+                trait_info.input_trait.vis.clone()
+            };
+            let module_prefix = util::prefix_in_child_module(prefix);
+            let trait_ident_lit = &trait_info.ident_lit;
+            let mock_fn_infos = trait_info
+                .methods
+                .iter()
+                .filter_map(Option::as_ref)
+                .map(|method| {
+                    let mirrored_attrs = method.mirrored_attrs();
+                    match &method.non_generic_mock_entry_ident {
+                        Some(non_generic_ident) => {
+                            let method_ident_lit = &method.ident_lit;
+                            let info_set_default_impl = if method.has_default_impl {
+                                Some(quote! { .default_impl() })
+                            } else {
+                                None
+                            };
+                            quote! {
+                                #(#mirrored_attrs)*
+                                || #module_prefix::private::generic_entry_info::<#non_generic_ident>()
+                                    .path(&[#trait_ident_lit, #method_ident_lit])
+                                    #info_set_default_impl
+                            }
+                        }
+                        None => {
+                            let mock_fn_ident = &method.mock_fn_ident;
+                            quote! { #(#mirrored_attrs)* <#mock_fn_ident as #module_prefix::MockFn>::info }
+                        }
+                    }
+                });
+            let supertrait_mocks = if attr.with_supertraits.is_some() {
+                Some(crate::export::mock_supertraits(
+                    trait_info.input_trait,
+                    &attr,
+                )?)
+            } else if attr.nested_api {
+                Some(crate::export::reexport_supertraits(trait_info.input_trait)?)
+            } else {
+                None
+            };
             (
                 Some(quote! {
                     #[doc = #mod_doc_lit_str]
                     #[allow(non_snake_case)]
                     #vis mod #module_ident {
                         #(#mock_fn_struct_items)*
+
+                        /// The [MockFnInfo](#module_prefix::MockFnInfo) of every method of the trait, e.g. for use with [Unimock::coverage_report](#module_prefix::Unimock::coverage_report).
+                        ///
+                        /// Generic methods are represented by their generic mock entry, covering all their instantiations.
+                        pub const ALL: &[fn() -> #module_prefix::MockFnInfo] = &[
+                            #(#mock_fn_infos),*
+                        ];
+
+                        #supertrait_mocks
                     }
                 }),
                 None,
@@ -163,7 +221,7 @@ pub fn generate(attr: Attr, item_trait: syn::ItemTrait) -> syn::Result<proc_macr
         ),
     };
 
-    let default_impl_delegator = if trait_info.has_default_impls {
+    let default_impl_delegator = if trait_info.has_default_impls && !attr.standalone_only {
         let non_default_methods = trait_info
             .methods
             .iter()
@@ -193,17 +251,29 @@ pub fn generate(attr: Attr, item_trait: syn::ItemTrait) -> syn::Result<proc_macr
         None
     };
 
-    let output_trait = trait_info.output_trait;
-
-    Ok(quote! {
-        #output_trait
-        #opt_mock_interface_public
-
-        // private part:
-        const _: () = {
-            #opt_mock_interface_private
-            #(#mock_fn_impl_details)*
+    let standalone = attr
+        .standalone
+        .as_ref()
+        .map(|standalone_ident| {
+            def_standalone(
+                standalone_ident,
+                &trait_info,
+                &attr,
+                &generic_params,
+                &generic_args,
+                &mirrored_impl_attributes,
+            )
+        })
+        .transpose()?;
+    let (standalone_public, standalone_private) = match standalone {
+        Some((public, private)) => (Some(public), Some(private)),
+        None => (None, None),
+    };
 
+    let unimock_impl = if attr.standalone_only {
+        None
+    } else {
+        Some(quote! {
             #impl_doc
             #(#mirrored_impl_attributes)*
             #impl_allow_lints
@@ -213,12 +283,134 @@ pub fn generate(attr: Attr, item_trait: syn::ItemTrait) -> syn::Result<proc_macr
                 #(#associated_futures)*
                 #(#method_impls)*
             }
+        })
+    };
+
+    let output_trait = trait_info.output_trait;
+
+    Ok(quote! {
+        #output_trait
+        #opt_mock_interface_public
+        #standalone_public
 
+        // private part:
+        const _: () = {
+            #opt_mock_interface_private
+            #(#mock_fn_impl_details)*
+
+            #unimock_impl
             #default_impl_delegator
+            #standalone_private
         };
     })
 }
 
+/// A newtype around `Unimock` which implements only the mocked trait, by delegating to `Unimock`.
+fn def_standalone(
+    standalone_ident: &syn::Ident,
+    trait_info: &TraitInfo,
+    attr: &Attr,
+    generic_params: &impl ToTokens,
+    generic_args: &impl ToTokens,
+    mirrored_impl_attributes: &[&syn::Attribute],
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let prefix = &attr.prefix;
+    let trait_path = &trait_info.trait_path;
+    let where_clause = &trait_info.input_trait.generics.where_clause;
+    let vis = &trait_info.input_trait.vis;
+    let impl_allow_lints = impl_allow_lints();
+
+    for method in trait_info.methods.iter().filter_map(Option::as_ref) {
+        if let Some(self_ty) = util::find_bare_self_type(&method.method.sig) {
+            return Err(syn::Error::new_spanned(
+                self_ty,
+                "A standalone mock cannot be generated for methods that use `Self` as a parameter or return type",
+            ));
+        }
+    }
+
+    let assoc_items = trait_info
+        .input_trait
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::TraitItem::Type(trait_item_type) => {
+                let ident = &trait_item_type.ident;
+                if let Some(attr_item_type) = attr.associated_types.get(&ident.to_string()) {
+                    return Some(quote! { #attr_item_type });
+                }
+                if attr.standalone_only {
+                    return None;
+                }
+                let generics = &trait_item_type.generics;
+                let (_, type_generics, where_clause) = generics.split_for_impl();
+                Some(quote! {
+                    type #ident #generics = <#prefix::Unimock as #trait_path #generic_args>::#ident #type_generics #where_clause;
+                })
+            }
+            syn::TraitItem::Const(trait_item_const) => {
+                let ident = &trait_item_const.ident;
+                if let Some(attr_item_const) = attr.associated_consts.get(&ident.to_string()) {
+                    return Some(quote! { #attr_item_const });
+                }
+                if attr.standalone_only {
+                    return None;
+                }
+                let ty = &trait_item_const.ty;
+                Some(quote! {
+                    const #ident: #ty = <#prefix::Unimock as #trait_path #generic_args>::#ident;
+                })
+            }
+            _ => None,
+        });
+    let associated_futures = trait_info
+        .methods
+        .iter()
+        .filter(|_| attr.standalone_only)
+        .filter_map(|method| associated_future::def_associated_future(method.as_ref()));
+
+    let method_impl_kind = if attr.standalone_only {
+        MethodImplKind::StandaloneMock
+    } else {
+        MethodImplKind::Standalone
+    };
+    let method_impls = trait_info
+        .methods
+        .iter()
+        .enumerate()
+        .map(|(index, method)| {
+            def_method_impl(index, method.as_ref(), trait_info, attr, method_impl_kind)
+        });
+
+    let doc_string = format!(
+        "Standalone mock of [{trait_path}], implementing only this trait by delegating to the wrapped `Unimock`.",
+        trait_path = path_to_string(trait_path),
+    );
+
+    let public = quote! {
+        #[doc = #doc_string]
+        #vis struct #standalone_ident(pub #prefix::Unimock);
+
+        impl ::core::convert::From<#prefix::Unimock> for #standalone_ident {
+            fn from(unimock: #prefix::Unimock) -> Self {
+                Self(unimock)
+            }
+        }
+    };
+
+    let private = quote! {
+        #(#mirrored_impl_attributes)*
+        #impl_allow_lints
+        impl #generic_params #trait_path #generic_args for #standalone_ident #where_clause {
+            #(#assoc_items)*
+            #(#associated_futures)*
+            #(#method_impls)*
+        }
+    };
+
+    Ok((public, private))
+}
+
 struct MockFnDef {
     mock_fn_struct_item: proc_macro2::TokenStream,
     impl_details: proc_macro2::TokenStream,
@@ -263,14 +455,168 @@ fn def_mock_fn(
         method.mockfn_doc_attrs(&trait_info.trait_path)
     };
 
-    let output_kind_assoc_type = method
-        .output_structure
-        .output_kind_assoc_type(prefix, trait_info, attr);
+    let output_kind_assoc_type = method.output_structure.output_kind_assoc_type(
+        prefix,
+        trait_info,
+        attr,
+        &quote! { #mock_fn_path #generic_args },
+    );
 
     let answer_fn_assoc_type = make_answer_fn(method, trait_info, attr);
 
     let debug_inputs_fn = method.generate_debug_inputs_fn(attr);
 
+    let mut_arg_targets = MutArgTarget::collect(method, trait_info, attr);
+    let mut_arg_bounds = mut_arg_targets
+        .iter()
+        .map(|MutArgTarget { index, target, .. }| {
+            quote! { + #prefix::input::MutArg<#index, Target = #target> }
+        });
+    let mut_arg_impls = mut_arg_targets.iter().map(|mut_arg_target| {
+        let MutArgTarget { index, target, .. } = mut_arg_target;
+        let access = if input_types_tuple.0.len() == 1 {
+            quote! { &mut **inputs }
+        } else {
+            let field = &mut_arg_target.field;
+            quote! { &mut *inputs.#field }
+        };
+        quote_spanned! { span=>
+            #impl_allow_lints
+            impl #generic_params #prefix::input::MutArg<#index> for #mock_fn_path #generic_args #where_clause {
+                type Target = #target;
+
+                fn mut_arg<'__a, #input_lifetime>(inputs: &'__a mut Self::Inputs<#input_lifetime>) -> &'__a mut Self::Target {
+                    #access
+                }
+            }
+        }
+    });
+
+    let lending_answer_impl = def_lending_answer_impl(method, trait_info, attr).map(|lending_answer| {
+        let LendingAnswer {
+            lent_ty,
+            owned_answer_fn,
+            answer_params,
+            shape,
+        } = lending_answer;
+
+        let lend = quote! {
+            |__owned: __O| <__O as ::core::borrow::Borrow<Self::Lent>>::borrow(
+                #prefix::Unimock::make_ref(__u, __owned)
+            )
+        };
+        let lend_owned = match shape {
+            LendingShape::Reference => quote! { (#lend)(__owned) },
+            LendingShape::Option | LendingShape::Result => quote! { __owned.map(#lend) },
+        };
+
+        quote_spanned! { span=>
+            #impl_allow_lints
+            impl #generic_params #prefix::output::LendingAnswer for #mock_fn_path #generic_args #where_clause {
+                type Lent = #lent_ty;
+                type OwnedAnswerFn<__O: 'static> = #owned_answer_fn;
+
+                fn lend_answer<__O>(answer_fn: &'static Self::OwnedAnswerFn<__O>) -> #prefix::alloc::Arc<Self::AnswerFn>
+                where
+                    __O: ::core::borrow::Borrow<Self::Lent> + Send + Sync + 'static,
+                {
+                    #prefix::alloc::Arc::new(move |__u, #(#answer_params),*| {
+                        let __owned = answer_fn(__u, #(#answer_params),*);
+                        #lend_owned
+                    })
+                }
+            }
+        }
+    });
+
+    let boxed_output_impls = method
+        .output_structure
+        .boxed_dyn_type(trait_info, attr)
+        .map(|dyn_ty| {
+            let dyn_bounds = match &dyn_ty {
+                syn::Type::TraitObject(trait_object) => trait_object.bounds.clone(),
+                _ => Default::default(),
+            };
+
+            let mut into_boxed_generics: syn::Generics =
+                syn::parse2(generic_params.to_token_stream()).unwrap_or_default();
+            into_boxed_generics.params.push(parse_quote!(__T));
+            into_boxed_generics
+                .make_where_clause()
+                .predicates
+                .extend(where_clause.iter().flat_map(|where_clause| where_clause.predicates.clone()));
+            into_boxed_generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(__T: #dyn_bounds + 'static));
+            let (into_boxed_params, _, into_boxed_where_clause) =
+                into_boxed_generics.split_for_impl();
+
+            quote_spanned! { span=>
+                #impl_allow_lints
+                impl #generic_params #prefix::output::BoxedOutput for #mock_fn_path #generic_args #where_clause {
+                    type Dyn = #dyn_ty;
+                }
+
+                #impl_allow_lints
+                impl #into_boxed_params #prefix::output::IntoBoxedOutput<__T> for #mock_fn_path #generic_args #into_boxed_where_clause {
+                    fn into_boxed(value: __T) -> #prefix::alloc::Box<Self::Dyn> {
+                        #prefix::alloc::Box::new(value)
+                    }
+                }
+            }
+        });
+
+    let returns_items_impl =
+        output::ItemsOutput::from_output(&method.method.sig).map(|items_output| {
+            let item_ty = util::self_type_to_unimock(items_output.item_ty, trait_info, attr);
+
+            let mut items_generics: syn::Generics =
+                syn::parse2(generic_params.to_token_stream()).unwrap_or_default();
+            items_generics
+                .make_where_clause()
+                .predicates
+                .extend(where_clause.iter().flat_map(|where_clause| where_clause.predicates.clone()));
+
+            let items_return = if items_output.lent {
+                // Items borrowed from `self` have a deep output kind made from a `Vec` of items
+                quote! {
+                    <#prefix::alloc::Vec<Self::Item> as #prefix::output::IntoReturn<Self::OutputKind>>::into_return(items)
+                }
+            } else {
+                let boxed_items = match &items_output.wrapping {
+                    output::ItemsWrapping::Iterator => quote! {
+                        #prefix::alloc::Box::new(::core::iter::IntoIterator::into_iter(items.clone()))
+                    },
+                    // Streams of items need the `futures-core-0-3` feature, which is not known to the macro
+                    output::ItemsWrapping::Stream => quote! {
+                        #prefix::private::pinned_item_stream!(items.clone())
+                    },
+                };
+
+                quote! {
+                    Ok(#prefix::private::owned_output(move || {
+                        let __output: <Self::OutputKind as #prefix::output::Return>::Type = #boxed_items;
+                        __output
+                    }))
+                }
+            };
+            let (_, _, items_where_clause) = items_generics.split_for_impl();
+
+            quote_spanned! { span=>
+                #impl_allow_lints
+                impl #generic_params #prefix::output::ReturnsItems for #mock_fn_path #generic_args #items_where_clause {
+                    type Item = #item_ty;
+
+                    fn items_return(
+                        items: #prefix::alloc::Vec<Self::Item>,
+                    ) -> ::core::result::Result<<Self::OutputKind as #prefix::output::Kind>::Return, #prefix::output::OutputError> {
+                        #items_return
+                    }
+                }
+            }
+        });
+
     let gen_mock_fn_struct_item = |non_generic_ident: &syn::Ident| {
         quote! {
             #[allow(non_camel_case_types)]
@@ -285,6 +631,37 @@ fn def_mock_fn(
         None
     };
 
+    let module_scope = match &attr.mock_api {
+        MockApi::MockMod(ident) => Some(quote_spanned! { span=> #ident:: }),
+        _ => None,
+    };
+
+    let info_set_generic = method
+        .non_generic_mock_entry_ident
+        .as_ref()
+        .map(|non_generic_ident| {
+            let type_args = trait_info
+                .input_trait
+                .generics
+                .params
+                .iter()
+                .chain(method.adapted_sig.generics.params.iter())
+                .filter_map(|generic_param| match generic_param {
+                    syn::GenericParam::Type(type_param) => {
+                        let ident = &type_param.ident;
+                        Some(quote! { ::core::any::type_name::<#ident>() })
+                    }
+                    syn::GenericParam::Const(const_param) => {
+                        let ident = &const_param.ident;
+                        Some(quote! { &#ident })
+                    }
+                    syn::GenericParam::Lifetime(_) => None,
+                });
+            quote! {
+                .generic::<#module_scope #non_generic_ident>(|| #prefix::alloc::vec![#(#prefix::alloc::ToString::to_string(#type_args)),*])
+            }
+        });
+
     let impl_block = quote_spanned! { span=>
         #(#mirrored_attrs)*
         #impl_allow_lints
@@ -297,22 +674,45 @@ fn def_mock_fn(
                 #prefix::MockFnInfo::new::<Self>()
                     .path(&[#trait_ident_lit, #method_ident_lit])
                     #info_set_default_impl
+                    #info_set_generic
             }
 
             #debug_inputs_fn
         }
+
+        #(#mut_arg_impls)*
+
+        #lending_answer_impl
+
+        #boxed_output_impls
+
+        #returns_items_impl
     };
 
     let mock_fn_def = if let Some(non_generic_ident) = &method.non_generic_mock_entry_ident {
         // the trait is generic
         let phantoms_tuple = util::MockFnPhantomsTuple { trait_info, method };
-        let untyped_phantoms =
-            iter_generic_type_params(trait_info, method).map(util::PhantomDataConstructor);
-        let module_scope = match &attr.mock_api {
-            MockApi::MockMod(ident) => Some(quote_spanned! { span=> #ident:: }),
-            _ => None,
+        let struct_params = util::Generics::struct_params(&trait_info.input_trait.generics, method);
+        let untyped_phantoms: Vec<_> = iter_generic_type_params(trait_info, method)
+            .map(util::PhantomDataConstructor)
+            .collect();
+        // Const generic parameters have no phantoms, so the struct may be a unit struct
+        let mock_fn_constructor = if untyped_phantoms.is_empty() {
+            quote! { #mock_fn_ident::#generic_args }
+        } else {
+            quote! { #mock_fn_ident::#generic_args(#(#untyped_phantoms),*) }
         };
         let answer_fn_assoc_type = make_answer_fn(method, trait_info, attr);
+        // A boxed output kind mentions the generic mock fn struct in the `with_types` signature
+        let generic_mock_fn_struct_vis = if method
+            .output_structure
+            .boxed_dyn_type(trait_info, attr)
+            .is_some()
+        {
+            Some(quote! { #[doc(hidden)] #mock_visibility })
+        } else {
+            None
+        };
 
         MockFnDef {
             mock_fn_struct_item: gen_mock_fn_struct_item(non_generic_ident),
@@ -320,21 +720,30 @@ fn def_mock_fn(
                 #impl_allow_lints
                 impl #module_scope #non_generic_ident {
                     #[doc = "Provide the generic parameters to the mocked method"]
+                    #[allow(clippy::implied_bounds_in_impls)]
                     pub fn with_types #generic_params(
                         self
                     ) -> impl for<#input_lifetime> #prefix::MockFn<
                         Inputs<#input_lifetime> = #input_types_tuple,
                         OutputKind = #output_kind_assoc_type,
                         AnswerFn = #answer_fn_assoc_type,
-                    >
+                    > #(#mut_arg_bounds)*
                         #where_clause
                     {
-                        #mock_fn_ident(#(#untyped_phantoms),*)
+                        #mock_fn_constructor
+                    }
+
+                    #[doc = "Respond to calls with any generic parameters, producing owned outputs from the given type factory"]
+                    pub fn with_any_types(
+                        self,
+                        factory: #prefix::factory::TypeFactory,
+                    ) -> #prefix::factory::AnyTypes {
+                        #prefix::factory::AnyTypes::new::<Self>(&[#trait_ident_lit, #method_ident_lit], factory)
                     }
                 }
 
                 #[allow(non_camel_case_types)]
-                struct #mock_fn_ident #generic_args #phantoms_tuple;
+                #generic_mock_fn_struct_vis struct #mock_fn_ident #struct_params #phantoms_tuple;
 
                 #impl_block
             },
@@ -349,9 +758,122 @@ fn def_mock_fn(
     Some(mock_fn_def)
 }
 
+struct LendingAnswer {
+    lent_ty: syn::Type,
+    owned_answer_fn: proc_macro2::TokenStream,
+    answer_params: Vec<syn::Ident>,
+    shape: LendingShape,
+}
+
+/// How the lent reference appears in the output type.
+enum LendingShape {
+    /// `&T`
+    Reference,
+    /// `Option<&T>`
+    Option,
+    /// `Result<&T, E>`
+    Result,
+}
+
+/// Methods that return a reference borrowed from `self`, optionally inside an `Option` or `Result`,
+/// can be answered with owned values, as long as the referenced type is free of lifetimes.
+fn def_lending_answer_impl(
+    method: &MockMethod,
+    trait_info: &TraitInfo,
+    attr: &Attr,
+) -> Option<LendingAnswer> {
+    if !matches!(
+        method.output_structure.wrapping,
+        output::OutputWrapping::None
+    ) || !matches!(
+        method.output_structure.output_kind,
+        output::OutputKind::SelfReference | output::OutputKind::Shallow
+    ) {
+        return None;
+    }
+
+    let receiver_lifetime = match method.method.sig.receiver() {
+        Some(syn::Receiver {
+            reference: Some((_, lifetime)),
+            ..
+        }) => lifetime.as_ref(),
+        _ => return None,
+    };
+
+    let (shape, output_reference, owned_ty): (_, _, syn::Type) =
+        match method.output_structure.output_type_stripped()? {
+            syn::Type::Reference(output_reference) => {
+                (LendingShape::Reference, output_reference, parse_quote!(__O))
+            }
+            syn::Type::Path(path) => {
+                let last_segment = path.path.segments.last()?;
+                let syn::PathArguments::AngleBracketed(angle) = &last_segment.arguments else {
+                    return None;
+                };
+                let mut type_args = angle.args.iter().map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                });
+                let Some(Some(syn::Type::Reference(output_reference))) = type_args.next() else {
+                    return None;
+                };
+
+                if last_segment.ident == "Option" && angle.args.len() == 1 {
+                    (
+                        LendingShape::Option,
+                        output_reference.clone(),
+                        parse_quote!(::core::option::Option<__O>),
+                    )
+                } else if last_segment.ident == "Result" && angle.args.len() == 2 {
+                    let error_ty = type_args.next()??;
+                    if util::contains_lifetime(error_ty.clone()) {
+                        return None;
+                    }
+                    let error_ty = util::self_type_to_unimock(error_ty.clone(), trait_info, attr);
+                    (
+                        LendingShape::Result,
+                        output_reference.clone(),
+                        parse_quote!(::core::result::Result<__O, #error_ty>),
+                    )
+                } else {
+                    return None;
+                }
+            }
+            _ => return None,
+        };
+
+    match (&output_reference.lifetime, receiver_lifetime) {
+        (None, _) => {}
+        (Some(output_lifetime), Some(receiver_lifetime))
+            if output_lifetime.ident == receiver_lifetime.ident => {}
+        _ => return None,
+    }
+
+    if output_reference.mutability.is_some()
+        || util::contains_lifetime(output_reference.elem.as_ref().clone())
+    {
+        return None;
+    }
+
+    let answer_params = (0..method.adapted_sig.inputs.len() - 1)
+        .map(|index| quote::format_ident!("__a{index}"))
+        .collect();
+
+    Some(LendingAnswer {
+        lent_ty: util::self_type_to_unimock(*output_reference.elem, trait_info, attr),
+        owned_answer_fn: make_owned_answer_fn(method, trait_info, attr, &owned_ty),
+        answer_params,
+        shape,
+    })
+}
+
+#[derive(Clone, Copy)]
 enum MethodImplKind {
     Mock,
     Delegate0,
+    Standalone,
+    /// The mock implementation on a standalone newtype, for when `Unimock` does not implement the trait
+    StandaloneMock,
 }
 
 fn def_method_impl(
@@ -410,8 +932,31 @@ fn def_method_impl(
         quote! { #[allow(#(#lints),*)] }
     };
 
+    let boxed_dyn_ty = method.output_structure.boxed_dyn_type(trait_info, attr);
+    // Non-mocked outputs of `impl Trait` methods must have the same type as the mocked ones
+    let box_output = |expr: proc_macro2::TokenStream| match &boxed_dyn_ty {
+        Some(dyn_ty) => quote! {
+            {
+                let __boxed: #prefix::alloc::Box<#dyn_ty> = #prefix::alloc::Box::new(#expr);
+                __boxed
+            }
+        },
+        None => expr,
+    };
+
+    // Boxed iterators and streams are returned from a `Vec` of items
+    let return_output = match &method.output_structure.wrapping {
+        output::OutputWrapping::BoxedIterator => quote! {
+            #prefix::alloc::Box::new(::core::iter::IntoIterator::into_iter(output))
+        },
+        output::OutputWrapping::PinnedStream => quote! {
+            #prefix::private::pinned_item_stream!(output)
+        },
+        _ => quote! { output },
+    };
+
     let body = match kind {
-        MethodImplKind::Mock => {
+        MethodImplKind::Mock | MethodImplKind::StandaloneMock => {
             let unmock_arm = attr.get_unmock_fn(index).map(
                 |UnmockFn {
                      path: unmock_path,
@@ -420,11 +965,14 @@ fn def_method_impl(
                     let fn_params =
                         method.inputs_destructuring(InputsSyntax::FnParams, Tupled(false), attr);
 
-                    let unmock_expr = match unmock_params {
-                        None => quote! {
+                    let unmock_expr = match (unmock_params, &receiver) {
+                        (None, Receiver::Static { .. }) => quote! {
+                            #unmock_path(#fn_params) #opt_dot_await
+                        },
+                        (None, _) => quote! {
                             #unmock_path(self, #fn_params) #opt_dot_await
                         },
-                        Some(UnmockFnParams { params }) => quote! {
+                        (Some(UnmockFnParams { params }), _) => quote! {
                             #unmock_path(#params) #opt_dot_await
                         },
                     };
@@ -435,6 +983,8 @@ fn def_method_impl(
                         attr,
                     );
 
+                    let unmock_expr = box_output(unmock_expr);
+
                     quote! {
                         #prefix::private::Eval::Continue(#prefix::private::Continuation::Unmock, #eval_pattern) => #unmock_expr,
                     }
@@ -446,7 +996,10 @@ fn def_method_impl(
             let fn_params =
                 method.inputs_destructuring(InputsSyntax::FnParams, Tupled(false), attr);
 
-            let default_delegator_call = if method.method.default.is_some() {
+            // There is no `DefaultImplDelegator` impl for a trait that `Unimock` does not implement
+            let default_delegator_call = if method.method.default.is_some()
+                && matches!(kind, MethodImplKind::Mock)
+            {
                 let delegator_path = quote! {
                     #prefix::private::DefaultImplDelegator
                 };
@@ -483,13 +1036,13 @@ fn def_method_impl(
                     InferImplTrait(false),
                 );
 
-                Some(quote! {
+                Some(box_output(quote! {
                     <#delegator_path as #trait_path #generic_args>::#method_ident(
                         #delegator_constructor,
                         #fn_params
                     )
                         #opt_dot_await
-                })
+                }))
             } else {
                 None
             };
@@ -509,12 +1062,16 @@ fn def_method_impl(
                     let fn_params_tupled =
                         method.inputs_destructuring(InputsSyntax::FnParams, Tupled(true), attr);
 
-                    let polonius_return_type: syn::Type = match method.method.sig.output.clone() {
-                        syn::ReturnType::Default => syn::parse_quote!(()),
-                        syn::ReturnType::Type(_arrow, ty) => {
-                            util::substitute_lifetimes(*ty, Some(&syn::parse_quote!('polonius)))
-                        }
-                    };
+                    let polonius_return_type: syn::Type =
+                        match (method.method.sig.output.clone(), &boxed_dyn_ty) {
+                            (syn::ReturnType::Default, _) => syn::parse_quote!(()),
+                            (syn::ReturnType::Type(..), Some(dyn_ty)) => {
+                                syn::parse_quote!(#prefix::alloc::Box<#dyn_ty>)
+                            }
+                            (syn::ReturnType::Type(_arrow, ty), None) => {
+                                util::substitute_lifetimes(*ty, Some(&syn::parse_quote!('polonius)))
+                            }
+                        };
 
                     let default_impl_input_eval_arm = if default_delegator_call.is_some() {
                         quote! {
@@ -529,7 +1086,7 @@ fn def_method_impl(
                     quote! {
                         let (__cont, #eval_pattern_all) = #prefix::polonius::_polonius!(|#self_ref| -> #polonius_return_type {
                             match #prefix::private::eval::<#mock_fn_path #eval_generic_args>(#self_ref, #inputs_eval_params) {
-                                #prefix::private::Eval::Return(output) => #prefix::polonius::_return!(output),
+                                #prefix::private::Eval::Return(output) => #prefix::polonius::_return!(#return_output),
                                 #prefix::private::Eval::Continue(__cont, #eval_pattern_no_mut) => #prefix::polonius::_exit!((__cont, #fn_params_tupled)),
                             }
                         });
@@ -549,7 +1106,7 @@ fn def_method_impl(
                         attr,
                     );
 
-                    let default_impl_delegate_arm = if method.method.default.is_some() {
+                    let default_impl_delegate_arm = if default_delegator_call.is_some() {
                         Some(quote! {
                             #prefix::private::Eval::Continue(#prefix::private::Continuation::CallDefaultImpl, #eval_pattern_no_mut) => {
                                 #default_delegator_call
@@ -559,11 +1116,16 @@ fn def_method_impl(
                         None
                     };
 
+                    let answer_receiver = match &receiver {
+                        Receiver::Static { surrogate_self } => quote! { &#surrogate_self },
+                        _ => quote! { self },
+                    };
+
                     quote_spanned! { span=>
                         match #prefix::private::eval::<#mock_fn_path #eval_generic_args>(#self_ref, #inputs_eval_params) {
-                            #prefix::private::Eval::Return(output) => output,
+                            #prefix::private::Eval::Return(output) => #return_output,
                             #prefix::private::Eval::Continue(#prefix::private::Continuation::Answer(__answer_fn), #eval_pattern_no_mut) => {
-                                __answer_fn(self, #fn_params)
+                                __answer_fn(#answer_receiver, #fn_params)
                             }
                             #unmock_arm
                             #default_impl_delegate_arm
@@ -573,6 +1135,31 @@ fn def_method_impl(
                 }
             }
         }
+        MethodImplKind::Standalone => {
+            let inputs_destructuring =
+                method.inputs_destructuring(InputsSyntax::FnParams, Tupled(false), attr);
+            let unimock_accessor = match &receiver {
+                Receiver::Owned => quote! { self.0, },
+                Receiver::Ref => quote! { &self.0, },
+                Receiver::MutRef { .. } => quote! { &mut self.0, },
+                Receiver::Pin { .. } => {
+                    quote! { ::core::pin::Pin::new(&mut ::core::pin::Pin::into_inner(self).0), }
+                }
+                Receiver::Static { .. } => quote! {},
+            };
+            let generic_args = util::Generics::trait_args(
+                &trait_info.input_trait.generics,
+                None,
+                InferImplTrait(false),
+            );
+            quote! {
+                <#prefix::Unimock as #trait_path #generic_args>::#method_ident(
+                    #unimock_accessor
+                    #inputs_destructuring
+                )
+                    #opt_dot_await
+            }
+        }
         MethodImplKind::Delegate0 => {
             let inputs_destructuring =
                 method.inputs_destructuring(InputsSyntax::FnParams, Tupled(false), attr);
@@ -592,7 +1179,7 @@ fn def_method_impl(
                     quote! {
                         {
                             <#unimock_type as #prefix::private::DelegateToDefaultImpl>::from_delegator(self)
-                        }
+                        },
                     }
                 }
                 Some(syn::Receiver {
@@ -600,15 +1187,16 @@ fn def_method_impl(
                     mutability: None,
                     ..
                 }) => {
-                    quote! { #prefix::private::as_ref(self) }
+                    quote! { #prefix::private::as_ref(self), }
                 }
                 Some(syn::Receiver {
                     reference: Some(_),
                     mutability: Some(_),
                     ..
                 }) => {
-                    quote! { #prefix::private::as_mut(self) }
+                    quote! { #prefix::private::as_mut(self), }
                 }
+                None if matches!(receiver, Receiver::Static { .. }) => quote! {},
                 _ => panic!("BUG: Incompatible receiver for default delegator"),
             };
             let generic_args = util::Generics::trait_args(
@@ -616,24 +1204,40 @@ fn def_method_impl(
                 None,
                 InferImplTrait(false),
             );
-            quote! {
+            let delegate_call = quote! {
                 <#prefix::Unimock as #trait_path #generic_args>::#method_ident(
-                    #unimock_accessor,
+                    #unimock_accessor
                     #inputs_destructuring
                 )
                     #opt_dot_await
+            };
+            match &method_sig.output {
+                // e.g. a constructor: the delegator is `Self` in this impl
+                syn::ReturnType::Type(_, output_ty) if matches!(output_ty.as_ref(), syn::Type::Path(type_path) if type_path.qself.is_none() && type_path.path.is_ident("Self")) =>
+                {
+                    quote! {
+                        #prefix::private::DefaultImplDelegator::__from_unimock(#delegate_call)
+                    }
+                }
+                _ => delegate_call,
             }
         }
     };
 
     let body = match (kind, &receiver) {
-        (MethodImplKind::Mock, Receiver::MutRef { surrogate_self }) => {
+        (
+            MethodImplKind::Mock | MethodImplKind::StandaloneMock,
+            Receiver::MutRef { surrogate_self },
+        ) => {
             quote! {
                 let mut #surrogate_self = self;
                 #body
             }
         }
-        (MethodImplKind::Mock, Receiver::Pin { surrogate_self }) => {
+        (
+            MethodImplKind::Mock | MethodImplKind::StandaloneMock,
+            Receiver::Pin { surrogate_self },
+        ) => {
             quote! {
                 let mut #surrogate_self = ::core::pin::Pin::into_inner(self);
                 #body
@@ -642,6 +1246,31 @@ fn def_method_impl(
         _ => body,
     };
 
+    // The mock body is written for a `Unimock` receiver, which the newtype wraps
+    let body = match kind {
+        MethodImplKind::StandaloneMock => {
+            let unimock_ident = syn::Ident::new("__unimock", span);
+            let body = util::replace_self_value(body, &unimock_ident);
+            let unimock_binding = match &receiver {
+                Receiver::Owned => Some(quote! { self.0 }),
+                Receiver::Ref => Some(quote! { &self.0 }),
+                Receiver::MutRef { .. } => Some(quote! { &mut self.0 }),
+                Receiver::Pin { .. } => Some(quote! {
+                    ::core::pin::Pin::new(&mut ::core::pin::Pin::into_inner(self).0)
+                }),
+                Receiver::Static { .. } => None,
+            };
+            match unimock_binding {
+                Some(binding) => quote! {
+                    let #unimock_ident = #binding;
+                    #body
+                },
+                None => body,
+            }
+        }
+        _ => body,
+    };
+
     let body = if must_async_wrap {
         quote_spanned! { span=>
             async move { #body }
@@ -650,6 +1279,20 @@ fn def_method_impl(
         body
     };
 
+    // The current instance is looked up when called, even if the output is a future
+    let body = match (kind, &receiver) {
+        (
+            MethodImplKind::Mock | MethodImplKind::StandaloneMock,
+            Receiver::Static { surrogate_self },
+        ) => {
+            quote! {
+                let #surrogate_self = #prefix::private::current_unimock::<#mock_fn_path #eval_generic_args>();
+                #body
+            }
+        }
+        _ => body,
+    };
+
     quote_spanned! { span=>
         #(#mirrored_attrs)*
         #track_caller
@@ -701,6 +1344,49 @@ impl InputTypesTuple {
     }
 }
 
+/// A `&mut T` parameter, which may be written to using `writes_arg`.
+struct MutArgTarget {
+    /// Parameter index, excluding the receiver
+    index: usize,
+    /// Field index into the inputs tuple
+    field: syn::Index,
+    target: syn::Type,
+}
+
+impl MutArgTarget {
+    fn collect(mock_method: &MockMethod, trait_info: &TraitInfo, attr: &Attr) -> Vec<Self> {
+        mock_method
+            .adapted_sig
+            .inputs
+            .iter()
+            .enumerate()
+            .filter_map(
+                |(index, input)| match mock_method.classify_arg(input, index) {
+                    ArgClass::Receiver => None,
+                    other => Some(other),
+                },
+            )
+            .enumerate()
+            .filter_map(|(index, arg_class)| match arg_class {
+                ArgClass::Other(_, syn::Type::Reference(reference))
+                    if reference.mutability.is_some() =>
+                {
+                    Some(Self {
+                        index,
+                        field: syn::Index::from(index),
+                        target: util::self_type_to_unimock(
+                            reference.elem.as_ref().clone(),
+                            trait_info,
+                            attr,
+                        ),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
 impl ToTokens for InputTypesTuple {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         if self.0.len() == 1 {
@@ -4,7 +4,10 @@ use syn::{parse_quote_spanned, spanned::Spanned, visit_mut::VisitMut, PathArgume
 
 use super::{
     trait_info::TraitInfo,
-    util::{find_future_bound, rename_lifetimes, self_type_to_unimock, RpitFuture},
+    util::{
+        contains_lifetime, find_future_bound, rename_lifetimes, self_type_to_unimock, RpitDyn,
+        RpitFuture,
+    },
     Attr,
 };
 
@@ -21,13 +24,31 @@ impl OutputStructure {
         prefix: &syn::Path,
         trait_info: &TraitInfo,
         attr: &Attr,
+        mock_fn_ty: &proc_macro2::TokenStream,
     ) -> proc_macro2::TokenStream {
+        if let OutputKind::Boxed = &self.output_kind {
+            // The mock fn itself knows how to box values into the trait object
+            return quote! {
+                #prefix::output::Boxing<#mock_fn_ty>
+            };
+        }
+
         let response_ty = self
             .output_ty_with_kind
             .self_type_to_unimock(trait_info, attr);
         self.render_associated_type(prefix, &response_ty)
     }
 
+    /// The trait object type of an `impl Trait` output.
+    pub fn boxed_dyn_type(&self, trait_info: &TraitInfo, attr: &Attr) -> Option<syn::Type> {
+        match (&self.output_kind, &self.output_ty_with_kind) {
+            (OutputKind::Boxed, AssociatedInnerType::Typed(dyn_ty)) => {
+                Some(self_type_to_unimock(dyn_ty.clone(), trait_info, attr))
+            }
+            _ => None,
+        }
+    }
+
     /// Type without future wrapping and without output::Kind injected
     pub fn output_type_stripped(&self) -> Option<syn::Type> {
         match &self.output_ty_stripped {
@@ -57,10 +78,15 @@ impl OutputStructure {
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 pub enum OutputWrapping {
     None,
     RpitFuture,
     AssociatedFuture(syn::TraitItemType),
+    /// Items are wrapped in a `Box<dyn Iterator>`
+    BoxedIterator,
+    /// Items are wrapped in a `Pin<Box<dyn Stream>>`
+    PinnedStream,
 }
 
 pub enum OutputKind {
@@ -71,6 +97,7 @@ pub enum OutputKind {
     StaticReference,
     Shallow,
     Deep,
+    Boxed,
 }
 
 impl OutputKind {
@@ -87,6 +114,7 @@ impl OutputKind {
             Self::StaticReference => "StaticRef",
             Self::Shallow => "Shallow",
             Self::Deep => "Deep",
+            Self::Boxed => "Boxing",
         }
     }
 }
@@ -94,9 +122,25 @@ impl OutputKind {
 pub fn determine_output_structure(
     sig: &syn::Signature,
     rpit_future: Option<RpitFuture>,
+    rpit_dyn: Option<RpitDyn>,
     item_trait: &syn::ItemTrait,
     attr: &Attr,
 ) -> OutputStructure {
+    if let (Some(rpit_dyn), syn::ReturnType::Type(_, output_ty)) = (rpit_dyn, &sig.output) {
+        return OutputStructure {
+            wrapping: OutputWrapping::None,
+            output_kind: OutputKind::Boxed,
+            output_ty_stripped: AssociatedInnerType::Typed(output_ty.as_ref().clone()),
+            output_ty_with_kind: AssociatedInnerType::Typed(rpit_dyn.dyn_ty),
+        };
+    }
+
+    if let syn::ReturnType::Type(_, output_ty) = &sig.output {
+        if let Some(output_structure) = determine_items_output_structure(sig, output_ty, attr) {
+            return output_structure;
+        }
+    }
+
     match &sig.output {
         syn::ReturnType::Default => OutputStructure {
             wrapping: OutputWrapping::None,
@@ -208,6 +252,148 @@ pub fn determine_owned_or_deep_output_structure(
     }
 }
 
+/// Determine output structure of boxed iterators and streams with items borrowed from `self`.
+///
+/// These are stored as a `Vec` of items, and a fresh iterator is made each time the method gets called.
+/// Boxed iterators and streams of owned items are [OutputKind::Owning], like any other owned value.
+fn determine_items_output_structure(
+    sig: &syn::Signature,
+    output_ty: &syn::Type,
+    attr: &Attr,
+) -> Option<OutputStructure> {
+    let prefix = &attr.prefix;
+    let (items_wrapping, item_ty) = boxed_items(output_ty)?;
+    let wrapping = match items_wrapping {
+        ItemsWrapping::Iterator => OutputWrapping::BoxedIterator,
+        ItemsWrapping::Stream => OutputWrapping::PinnedStream,
+    };
+
+    let mut inner_item_ty = item_ty.clone();
+    let borrow_info = ReturnTypeAnalyzer::analyze_borrows(sig, &mut inner_item_ty);
+    if !(borrow_info.has_elided_reference || borrow_info.has_self_reference) {
+        return None;
+    }
+
+    let (kind, ty) = make_generic_kind(inner_item_ty, attr);
+    let item_kind_ty = wrap_output_kind(kind, ty, attr);
+
+    Some(OutputStructure {
+        wrapping,
+        output_kind: OutputKind::Deep,
+        output_ty_stripped: AssociatedInnerType::Typed(output_ty.clone()),
+        output_ty_with_kind: AssociatedInnerType::Typed(parse_quote_spanned! { output_ty.span()=>
+            #prefix::alloc::Box<dyn ::core::iter::Iterator<Item = #item_kind_ty>>
+        }),
+    })
+}
+
+pub enum ItemsWrapping {
+    /// `Box<dyn Iterator<Item = T>>`
+    Iterator,
+    /// `Pin<Box<dyn Stream<Item = T>>>`
+    Stream,
+}
+
+/// The items of a boxed iterator or stream output, which can be responded to with `returns_items`.
+pub struct ItemsOutput {
+    pub wrapping: ItemsWrapping,
+    /// The stored item type, which is `T` when the items are `&T`.
+    pub item_ty: syn::Type,
+    /// Whether the items are references to the stored items.
+    pub lent: bool,
+}
+
+impl ItemsOutput {
+    pub fn from_output(sig: &syn::Signature) -> Option<Self> {
+        let output_ty = match &sig.output {
+            syn::ReturnType::Type(_, output_ty) => output_ty,
+            syn::ReturnType::Default => return None,
+        };
+        let (wrapping, item_ty) = boxed_items(output_ty)?;
+
+        match item_ty {
+            syn::Type::Reference(reference)
+                if reference.mutability.is_none()
+                    && !contains_lifetime(reference.elem.as_ref().clone()) =>
+            {
+                Some(Self {
+                    wrapping,
+                    item_ty: reference.elem.as_ref().clone(),
+                    lent: true,
+                })
+            }
+            item_ty if !contains_lifetime(item_ty.clone()) => Some(Self {
+                wrapping,
+                item_ty: item_ty.clone(),
+                lent: false,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The item type of a boxed iterator or stream.
+fn boxed_items(output_ty: &syn::Type) -> Option<(ItemsWrapping, &syn::Type)> {
+    if let Some(dyn_ty) = generic_arg_of(output_ty, "Box") {
+        let item_ty = dyn_item_type(dyn_ty, "Iterator")?;
+        Some((ItemsWrapping::Iterator, item_ty))
+    } else {
+        let dyn_ty = generic_arg_of(generic_arg_of(output_ty, "Pin")?, "Box")?;
+        let item_ty = dyn_item_type(dyn_ty, "Stream")?;
+        Some((ItemsWrapping::Stream, item_ty))
+    }
+}
+
+/// The single generic type argument of a type path whose last segment is `ident`.
+fn generic_arg_of<'t>(ty: &'t syn::Type, ident: &str) -> Option<&'t syn::Type> {
+    let last_segment = match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            type_path.path.segments.last()?
+        }
+        _ => return None,
+    };
+    if last_segment.ident != ident {
+        return None;
+    }
+
+    match &last_segment.arguments {
+        PathArguments::AngleBracketed(angle) if angle.args.len() == 1 => {
+            match angle.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The `Item` type of a `dyn Trait<Item = T>`, where the trait name is `trait_ident`.
+fn dyn_item_type<'t>(ty: &'t syn::Type, trait_ident: &str) -> Option<&'t syn::Type> {
+    let trait_object = match ty {
+        syn::Type::TraitObject(trait_object) => trait_object,
+        _ => return None,
+    };
+
+    trait_object.bounds.iter().find_map(|bound| {
+        let trait_path = match bound {
+            syn::TypeParamBound::Trait(trait_bound) => &trait_bound.path,
+            _ => return None,
+        };
+        let last_segment = trait_path.segments.last()?;
+        if last_segment.ident != trait_ident {
+            return None;
+        }
+
+        match &last_segment.arguments {
+            PathArguments::AngleBracketed(angle) => angle.args.iter().find_map(|arg| match arg {
+                syn::GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(&assoc.ty),
+                _ => None,
+            }),
+            _ => None,
+        }
+    })
+}
+
 fn make_generic_kind(ty: syn::Type, attr: &Attr) -> (OutputKind, syn::Type) {
     match ty {
         syn::Type::Reference(reference) => {
@@ -348,6 +534,7 @@ fn find_param_lifetime(sig: &syn::Signature, lifetime_ident: &syn::Ident) -> Opt
 }
 
 #[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
 enum AssociatedInnerType {
     Unit,
     Typed(syn::Type),
@@ -487,10 +674,10 @@ fn add_dyn_static_bound(ty: &mut syn::Type) {
                         let mut tmp = Box::new(syn::Type::Verbatim(TokenStream::new()));
                         std::mem::swap(&mut tmp, &mut ty.elem);
 
-                        ty.elem = Box::new(syn::Type::Paren(syn::TypeParen {
+                        *ty.elem = syn::Type::Paren(syn::TypeParen {
                             paren_token: syn::token::Paren::default(),
                             elem: tmp,
-                        }));
+                        });
                     }
                 }
             }
@@ -33,8 +33,8 @@ impl<'t> TraitInfo<'t> {
             )
         });
 
-        let ident_lit =
-            syn::LitStr::new(&format!("{}", &input_trait.ident), input_trait.ident.span());
+        let display_ident = attr.display_ident.as_ref().unwrap_or(&input_trait.ident);
+        let ident_lit = syn::LitStr::new(&format!("{}", display_ident), display_ident.span());
 
         let (output_trait, trait_path) = if let Some(mirror) = &attr.mirror {
             (None, mirror.clone())
@@ -44,6 +44,7 @@ enum GenericsKind {
     FnParams(ContainsAsync),
     TraitArgs(InferImplTrait),
     FnArgs(InferImplTrait),
+    StructParams,
 }
 
 #[derive(Clone, Copy)]
@@ -52,17 +53,33 @@ pub struct IncludeTraitLifetimes(pub bool);
 #[derive(Clone, Copy)]
 pub struct InferImplTrait(pub bool);
 
+#[derive(Clone, Copy)]
+pub struct DeclareConsts(pub bool);
+
+fn const_arg(
+    const_param: &syn::ConstParam,
+    declare_consts: DeclareConsts,
+) -> proc_macro2::TokenStream {
+    let ident = &const_param.ident;
+    if declare_consts.0 {
+        let ty = &const_param.ty;
+        quote! { const #ident: #ty }
+    } else {
+        quote! { #ident }
+    }
+}
+
 pub fn is_generic(generics: &syn::Generics) -> IsGeneric {
     IsGeneric(!generics.params.is_empty())
 }
 
 pub fn is_type_generic(generics: &syn::Generics) -> IsTypeGeneric {
-    IsTypeGeneric(
-        generics
-            .params
-            .iter()
-            .any(|param| matches!(param, syn::GenericParam::Type(_))),
-    )
+    IsTypeGeneric(generics.params.iter().any(|param| {
+        matches!(
+            param,
+            syn::GenericParam::Type(_) | syn::GenericParam::Const(_)
+        )
+    }))
 }
 
 fn are_any_generic(trait_generics: &syn::Generics, method: Option<&MockMethod<'_>>) -> IsGeneric {
@@ -156,29 +173,43 @@ impl<'t> Generics<'t> {
         }
     }
 
+    // Params of the generic mock fn struct: e.g. struct S<A, const N: usize>
+    pub fn struct_params(trait_generics: &'t syn::Generics, method: &'t MockMethod<'t>) -> Self {
+        Self {
+            trait_generics,
+            method: Some(method),
+            kind: if are_any_type_generic(trait_generics, Some(method)).0 {
+                GenericsKind::StructParams
+            } else {
+                GenericsKind::None
+            },
+        }
+    }
+
     fn args_iterator(
         &self,
         include_trait_lifetimes: IncludeTraitLifetimes,
         infer_impl_trait: InferImplTrait,
+        declare_consts: DeclareConsts,
     ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
         self.trait_generics
             .params
             .iter()
-            .map(move |trait_param| match trait_param {
-                syn::GenericParam::Lifetime(lifetime) => {
+            .filter_map(move |trait_param| match trait_param {
+                syn::GenericParam::Lifetime(lifetime_param) => {
                     if include_trait_lifetimes.0 {
-                        quote! { #lifetime }
+                        let lifetime = &lifetime_param.lifetime;
+                        Some(quote! { #lifetime })
                     } else {
-                        quote! {}
+                        None
                     }
                 }
                 syn::GenericParam::Type(type_param) => {
                     let ident = &type_param.ident;
-                    quote! { #ident }
+                    Some(quote! { #ident })
                 }
                 syn::GenericParam::Const(const_param) => {
-                    let ident = &const_param.ident;
-                    quote! { #ident }
+                    Some(const_arg(const_param, declare_consts))
                 }
             })
             .chain(
@@ -205,8 +236,7 @@ impl<'t> Generics<'t> {
                             }
                         }
                         syn::GenericParam::Const(const_param) => {
-                            let ident = &const_param.ident;
-                            Some(quote! { #ident })
+                            Some(const_arg(const_param, declare_consts))
                         }
                     }),
             )
@@ -251,14 +281,33 @@ impl<'t> quote::ToTokens for Generics<'t> {
                 }
             }
             GenericsKind::TraitArgs(infer_impl_trait) => {
-                let args = self.args_iterator(IncludeTraitLifetimes(true), *infer_impl_trait);
+                let args = self.args_iterator(
+                    IncludeTraitLifetimes(true),
+                    *infer_impl_trait,
+                    DeclareConsts(false),
+                );
                 quote! {
                     #(#args),*
                 }
                 .to_tokens(tokens);
             }
             GenericsKind::FnArgs(infer_impl_trait) => {
-                let args = self.args_iterator(IncludeTraitLifetimes(false), *infer_impl_trait);
+                let args = self.args_iterator(
+                    IncludeTraitLifetimes(false),
+                    *infer_impl_trait,
+                    DeclareConsts(false),
+                );
+                quote! {
+                    #(#args),*
+                }
+                .to_tokens(tokens);
+            }
+            GenericsKind::StructParams => {
+                let args = self.args_iterator(
+                    IncludeTraitLifetimes(false),
+                    InferImplTrait(false),
+                    DeclareConsts(true),
+                );
                 quote! {
                     #(#args),*
                 }
@@ -543,20 +592,28 @@ pub fn write_generic_params_with_bounds(
     tokens: &mut proc_macro2::TokenStream,
 ) {
     for generic_param in generics.params.iter() {
-        if let syn::GenericParam::Type(type_param) = generic_param {
-            let mut bounded_param = type_param.clone();
+        match generic_param {
+            syn::GenericParam::Type(type_param) => {
+                let mut bounded_param = type_param.clone();
 
-            add_static_bound_if_not_present(&mut bounded_param);
-            if contains_async.0 {
-                add_send_bound_if_not_present(&mut bounded_param);
-            }
+                add_static_bound_if_not_present(&mut bounded_param);
+                if contains_async.0 {
+                    add_send_bound_if_not_present(&mut bounded_param);
+                }
 
-            sep_tracker.comma_sep(tokens);
-            bounded_param.to_tokens(tokens);
+                sep_tracker.comma_sep(tokens);
+                bounded_param.to_tokens(tokens);
+            }
+            syn::GenericParam::Const(const_param) => {
+                sep_tracker.comma_sep(tokens);
+                const_arg(const_param, DeclareConsts(true)).to_tokens(tokens);
+            }
+            syn::GenericParam::Lifetime(_) => {}
         }
     }
 }
 
+/// Mock fns are identified by their `TypeId`, which only exists for `'static` types.
 fn add_static_bound_if_not_present(type_param: &mut syn::TypeParam) {
     let has_static_bound = type_param.bounds.iter().any(|bound| match bound {
         syn::TypeParamBound::Lifetime(lifetime) => lifetime.ident == "static",
@@ -609,6 +666,81 @@ pub fn replace_self_ty_with_path(mut ty: syn::Type, replacement_path: &syn::Path
     ty
 }
 
+/// Replace the `self` value in generated tokens with `replacement`, leaving `self::` paths as they are.
+pub fn replace_self_value(
+    tokens: proc_macro2::TokenStream,
+    replacement: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    use proc_macro2::TokenTree;
+
+    let mut output = proc_macro2::TokenStream::new();
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tree) = iter.next() {
+        match tree {
+            TokenTree::Ident(ident) if ident == "self" => {
+                let is_path =
+                    matches!(iter.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == ':');
+                if is_path {
+                    output.extend([TokenTree::Ident(ident)]);
+                } else {
+                    output.extend([TokenTree::Ident(replacement.clone())]);
+                }
+            }
+            TokenTree::Group(group) => {
+                let mut replaced = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_self_value(group.stream(), replacement),
+                );
+                replaced.set_span(group.span());
+                output.extend([TokenTree::Group(replaced)]);
+            }
+            tree => output.extend([tree]),
+        }
+    }
+    output
+}
+
+/// The unimock prefix as seen from a module nested in the module of the mocked trait.
+pub fn prefix_in_child_module(prefix: &syn::Path) -> syn::Path {
+    let mut prefix = prefix.clone();
+    let is_relative = prefix.leading_colon.is_none()
+        && !matches!(prefix.segments.first(), Some(segment) if segment.ident == "crate" || segment.ident == "$crate");
+    if is_relative {
+        prefix.segments.insert(0, syn::parse_quote! { super });
+    }
+    prefix
+}
+
+/// Find a bare `Self` type among the non-receiver parameters or the return type of a method.
+pub fn find_bare_self_type(sig: &syn::Signature) -> Option<syn::Path> {
+    struct Finder {
+        found: Option<syn::Path>,
+    }
+
+    impl syn::visit_mut::VisitMut for Finder {
+        fn visit_qself_mut(&mut self, _: &mut syn::QSelf) {}
+
+        fn visit_type_path_mut(&mut self, type_path: &mut syn::TypePath) {
+            if type_path.qself.is_none() && type_path.path.is_ident("Self") {
+                self.found.get_or_insert_with(|| type_path.path.clone());
+            }
+
+            syn::visit_mut::visit_type_path_mut(self, type_path);
+        }
+    }
+
+    let mut finder = Finder { found: None };
+    let mut sig = sig.clone();
+    for input in &mut sig.inputs {
+        if let syn::FnArg::Typed(pat_type) = input {
+            finder.visit_type_mut(&mut pat_type.ty);
+        }
+    }
+    finder.visit_return_type_mut(&mut sig.output);
+
+    finder.found
+}
+
 pub struct FutureBound<'s> {
     pub output: &'s syn::AssocType,
 }
@@ -652,6 +784,53 @@ pub struct RpitFuture {
     pub output: syn::AssocType,
 }
 
+/// A return-position `impl Trait` that is not a future, represented as a trait object.
+pub struct RpitDyn {
+    pub dyn_ty: syn::Type,
+}
+
+impl RpitDyn {
+    /// Convert `impl A + Send + 'a` into `dyn A + Send`.
+    ///
+    /// Only the auto traits written by the user are kept, as other implementations,
+    /// like unmock functions and default bodies, may return values that don't implement any others.
+    pub fn dyn_type(impl_trait: &syn::TypeImplTrait) -> syn::Result<syn::Type> {
+        let mut bounds: syn::punctuated::Punctuated<syn::TypeParamBound, syn::token::Plus> =
+            Default::default();
+        let mut has_non_auto_trait = false;
+
+        for bound in &impl_trait.bounds {
+            if let syn::TypeParamBound::Trait(trait_bound) = bound {
+                if !is_auto_trait(&trait_bound.path) {
+                    if has_non_auto_trait {
+                        return Err(syn::Error::new_spanned(
+                            trait_bound,
+                            "Unimock cannot represent an `impl Trait` with more than one non-auto trait as a trait object. Define a trait with the others as supertraits, and return that instead.",
+                        ));
+                    }
+                    has_non_auto_trait = true;
+                }
+                bounds.push(bound.clone());
+            }
+        }
+
+        Ok(syn::Type::TraitObject(syn::TypeTraitObject {
+            dyn_token: Some(Default::default()),
+            bounds,
+        }))
+    }
+}
+
+fn is_auto_trait(path: &syn::Path) -> bool {
+    match path.segments.last() {
+        Some(last_segment) => matches!(
+            last_segment.ident.to_string().as_str(),
+            "Send" | "Sync" | "Unpin" | "UnwindSafe" | "RefUnwindSafe"
+        ),
+        None => false,
+    }
+}
+
 pub fn guess_is_pin(ty: &syn::Type) -> bool {
     if let syn::Type::Path(type_path) = ty {
         if let Some(last_segment) = type_path.path.segments.last() {